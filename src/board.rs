@@ -0,0 +1,1369 @@
+/*****************************************************************/
+//! [Board]
+/*****************************************************************/
+//!
+//! The simulation itself, free of any windowing or graphics, so that
+//! it may run just the same with or without a window. A board holds
+//! the state of every cell along with the rule and edge behaviour it
+//! is advanced under, one generation at a time.
+/*****************************************************************/
+
+use cells::{Cells, WORD_BITS};
+use stats;
+use std::collections::HashSet;
+
+/// Number of cells an expanding board grows by along each side it grows.
+pub const GROWTH: usize = 64;
+
+/// Width and height an expanding board never grows beyond, in cells.
+pub const MAX_SIDE: usize = 4096;
+
+/// [Rule]
+/// A life-like rule, in which the fate of each cell depends solely on
+/// whether it is alive and on its number of living neighbours.
+///
+/// Fields:
+/// [birth] Whether a dead cell with the indexed number of neighbours is born;
+/// [survival] Whether a living cell with the indexed number of neighbours survives.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Rule {
+    pub birth: [bool; 9],
+    pub survival: [bool; 9]
+}
+
+impl std::fmt::Display for Rule {
+
+    /// [Fmt]
+    /// Writes the rule in B/S notation, as read by `parse`.
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let digits = |table: &[bool; 9]| -> String {
+            (0..9).filter(|&count| table[count]).map(|count| count.to_string()).collect()
+        };
+        write!(f, "B{}/S{}", digits(&self.birth), digits(&self.survival))
+    }
+}
+
+/// [Presets]
+/// Famous life-like rules, by name, in the order they are cycled through.
+pub const PRESETS: [(&str, Rule); 5] = [
+    ("Conway", Rule::from_counts(&[3], &[2, 3])),
+    ("HighLife", Rule::from_counts(&[3, 6], &[2, 3])),
+    ("Seeds", Rule::from_counts(&[2], &[])),
+    ("Day & Night", Rule::from_counts(&[3, 6, 7, 8], &[3, 4, 6, 7, 8])),
+    ("Replicator", Rule::from_counts(&[1, 3, 5, 7], &[1, 3, 5, 7])),
+];
+
+impl Rule {
+
+    /// [From Counts]
+    /// Builds a rule from the neighbour counts on which cells are born,
+    /// and on which they survive. Every count must be at most 8.
+    pub const fn from_counts(birth: &[usize], survival: &[usize]) -> Rule {
+        let mut rule = Rule { birth: [false; 9], survival: [false; 9] };
+        let mut i = 0;
+        while i < birth.len() {
+            rule.birth[birth[i]] = true;
+            i += 1;
+        }
+        let mut i = 0;
+        while i < survival.len() {
+            rule.survival[survival[i]] = true;
+            i += 1;
+        }
+        rule
+    }
+
+    /// [Name]
+    /// Name of the preset matching this rule, if there is one.
+    pub fn name(self) -> Option<&'static str> {
+        PRESETS.iter().find(|&&(_, preset)| preset == self).map(|&(name, _)| name)
+    }
+
+    /// [Conway]
+    /// Conway's original rule, B3/S23.
+    pub fn conway() -> Rule {
+        Rule::parse("B3/S23").unwrap()
+    }
+
+    /// [Next]
+    /// Whether a cell, alive or dead, with the given number of living
+    /// neighbours is alive in the next generation.
+    #[inline]
+    pub fn next(self, alive: bool, neighbours: usize) -> bool {
+        if alive {
+            self.survival[neighbours]
+        } else {
+            self.birth[neighbours]
+        }
+    }
+
+    /// [Parse]
+    /// Parses a rule string in B/S notation, such as `B36/S23`, into its
+    /// birth and survival tables. The letters are case-insensitive, and
+    /// either half may be empty (as in `B2/S`), but both must be present.
+    pub fn parse(rule: &str) -> Result<Rule, String> {
+        let halves: Vec<&str> = rule.trim().split('/').collect();
+        if halves.len() != 2 {
+            return Err(format!("The rule \"{}\" must be of the form B<digits>/S<digits>.", rule));
+        }
+
+        let mut parsed = Rule { birth: [false; 9], survival: [false; 9] };
+        let (mut has_birth, mut has_survival) = (false, false);
+        for half in halves {
+            let mut chars = half.chars();
+            let table = match chars.next().map(|c| c.to_ascii_uppercase()) {
+                Some('B') if !has_birth => { has_birth = true; &mut parsed.birth },
+                Some('S') if !has_survival => { has_survival = true; &mut parsed.survival },
+                _ => return Err(format!("The rule \"{}\" must be of the form B<digits>/S<digits>.", rule)),
+            };
+            for c in chars {
+                match c.to_digit(10) {
+                    Some(count) if count <= 8 => table[count as usize] = true,
+                    _ => return Err(format!("The rule \"{}\" contains an invalid neighbour count '{}'.", rule, c)),
+                }
+            }
+        }
+
+        Ok(parsed)
+    }
+}
+
+/// [Larger Than Life]
+/// A totalistic rule over a larger neighbourhood, in which each cell
+/// counts the living cells within a square of the given Chebyshev radius
+/// around it, and is born or survives while that count lies within a
+/// range. Conway's rule is the special case of radius 1, with births on
+/// 3 and survival on 2 to 3 neighbours.
+///
+/// Fields:
+/// [radius] Greatest distance along either axis at which a cell counts as a neighbour;
+/// [middle] Whether each cell counts itself among its neighbours;
+/// [birth] Least and greatest number of neighbours on which a dead cell is born;
+/// [survival] Least and greatest number of neighbours on which a living cell survives.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LargerThanLife {
+    pub radius: usize,
+    pub middle: bool,
+    pub birth: (usize, usize),
+    pub survival: (usize, usize)
+}
+
+impl std::fmt::Display for LargerThanLife {
+
+    /// [Fmt]
+    /// Writes the rule in Golly's notation, as read by `parse`.
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "R{},C0,M{},S{}..{},B{}..{},NM", self.radius, self.middle as u8,
+               self.survival.0, self.survival.1, self.birth.0, self.birth.1)
+    }
+}
+
+impl LargerThanLife {
+
+    /// [Next]
+    /// Whether a cell, alive or dead, with the given number of living
+    /// neighbours is alive in the next generation.
+    #[inline]
+    pub fn next(self, alive: bool, neighbours: usize) -> bool {
+        let (least, greatest) = if alive { self.survival } else { self.birth };
+        (least..=greatest).contains(&neighbours)
+    }
+
+    /// [Parse]
+    /// Parses a rule in Golly's notation for Larger than Life, such as
+    /// Bosco's rule, `R5,C0,M1,S34..58,B34..45,NM`. `R` gives the radius,
+    /// `C` the number of states (0 or 2, as only two are supported), `M`
+    /// whether cells count themselves, `S` and `B` the survival and birth
+    /// ranges, and `N` the neighbourhood, of which only `NM` (a square) is
+    /// supported. Every field but the radius and ranges may be left out.
+    pub fn parse(rule: &str) -> Result<LargerThanLife, String> {
+        let invalid = |field: &str| format!("The Larger than Life rule \"{}\" has an invalid field \"{}\".", rule, field);
+        let range = |field: &str, value: &str| -> Result<(usize, usize), String> {
+            let mut bounds = value.splitn(2, "..").map(|bound| bound.parse::<usize>());
+            match (bounds.next(), bounds.next()) {
+                (Some(Ok(least)), Some(Ok(greatest))) if least <= greatest => Ok((least, greatest)),
+                _ => Err(invalid(field)),
+            }
+        };
+
+        let (mut radius, mut middle, mut birth, mut survival) = (None, false, None, None);
+        for field in rule.trim().split(',').map(str::trim) {
+            let (key, value) = field.split_at(field.find(|c: char| !c.is_ascii_alphabetic()).unwrap_or(field.len()));
+            match (key.to_ascii_uppercase().as_str(), value) {
+                ("R", value) => radius = Some(value.parse::<usize>().ok().filter(|&radius| radius > 0).ok_or_else(|| invalid(field))?),
+                ("C", "0") | ("C", "2") => {},
+                ("M", "0") => middle = false,
+                ("M", "1") => middle = true,
+                ("S", value) => survival = Some(range(field, value)?),
+                ("B", value) => birth = Some(range(field, value)?),
+                ("NM", "") => {},
+                _ => return Err(invalid(field)),
+            }
+        }
+
+        match (radius, birth, survival) {
+            (Some(radius), Some(birth), Some(survival)) => Ok(LargerThanLife { radius, middle, birth, survival }),
+            _ => Err(format!("The Larger than Life rule \"{}\" must give a radius (R), and birth (B) and survival (S) ranges.", rule)),
+        }
+    }
+}
+
+/// [Neighbourhood]
+/// Which of the cells surrounding a cell count as its neighbours.
+///
+/// Variants:
+/// [Moore] The eight cells surrounding it, including diagonals;
+/// [VonNeumann] The four cells orthogonally adjacent to it;
+/// [Diagonal] The four cells diagonally adjacent to it.
+///
+/// Diagonal neighbours always share the parity of their column plus row,
+/// so under the diagonal neighbourhood the board splits into two
+/// interleaved checkerboards which never interact, each evolving as if
+/// the other were not there. The one exception is a torus an odd number
+/// of cells across or down, where wrapping around joins the two.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Neighbourhood {
+    Moore,
+    VonNeumann,
+    Diagonal
+}
+
+impl Neighbourhood {
+
+    /// [Next]
+    /// The neighbourhood following this one, in the order they are cycled through.
+    pub fn next(self) -> Neighbourhood {
+        match self {
+            Neighbourhood::Moore => Neighbourhood::VonNeumann,
+            Neighbourhood::VonNeumann => Neighbourhood::Diagonal,
+            Neighbourhood::Diagonal => Neighbourhood::Moore,
+        }
+    }
+}
+
+impl std::str::FromStr for Neighbourhood {
+    type Err = String;
+
+    /// [From Str]
+    /// Parses the name of a neighbourhood, which is case-insensitive.
+    fn from_str(neighbourhood: &str) -> Result<Neighbourhood, String> {
+        match neighbourhood.to_ascii_lowercase().as_str() {
+            "moore" => Ok(Neighbourhood::Moore),
+            "vonneumann" | "von-neumann" => Ok(Neighbourhood::VonNeumann),
+            "diagonal" => Ok(Neighbourhood::Diagonal),
+            _ => Err(format!("The neighbourhood \"{}\" must be one of moore, von-neumann or diagonal.", neighbourhood)),
+        }
+    }
+}
+
+/// [Boundary]
+/// What lies beyond the edges of the board.
+///
+/// Variants:
+/// [Torus] The opposite edge, with the board wrapping around;
+/// [Dead] Nothing, with every cell beyond the edges counting as dead;
+/// [Reflect] A mirror image, with every cell beyond an edge matching the nearest cell on it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Boundary {
+    Torus,
+    Dead,
+    Reflect
+}
+
+impl Boundary {
+
+    /// [Next]
+    /// The boundary following this one, in the order they are cycled through.
+    pub fn next(self) -> Boundary {
+        match self {
+            Boundary::Torus => Boundary::Dead,
+            Boundary::Dead => Boundary::Reflect,
+            Boundary::Reflect => Boundary::Torus,
+        }
+    }
+}
+
+impl std::str::FromStr for Boundary {
+    type Err = String;
+
+    /// [From Str]
+    /// Parses the name of a boundary, which is case-insensitive.
+    fn from_str(boundary: &str) -> Result<Boundary, String> {
+        match boundary.to_ascii_lowercase().as_str() {
+            "torus" => Ok(Boundary::Torus),
+            "dead" => Ok(Boundary::Dead),
+            "reflect" => Ok(Boundary::Reflect),
+            _ => Err(format!("The boundary \"{}\" must be one of torus, dead or reflect.", boundary)),
+        }
+    }
+}
+
+/// [Colouring]
+/// How living cells are coloured, in the variants of Life with more than
+/// one kind of living cell. Colours are numbered from one, with zero
+/// meaning a dead cell.
+///
+/// Variants:
+/// [Immigration] Two colours, with each newborn cell taking the colour of most of its parents;
+/// [QuadLife] Four colours, likewise, except that a cell born of three parents of different
+/// colours takes the fourth.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Colouring {
+    Immigration,
+    QuadLife
+}
+
+impl Colouring {
+
+    /// [Colours]
+    /// Number of colours living cells may take.
+    pub fn colours(self) -> usize {
+        match self {
+            Colouring::Immigration => 2,
+            Colouring::QuadLife => 4,
+        }
+    }
+
+    /// [Newborn]
+    /// Colour of a cell born beside living neighbours of each colour, as
+    /// counted in `votes`, with the first colour's count first. The colour
+    /// most neighbours share wins. Should several tie, under QuadLife three
+    /// parents of different colours give the fourth, and otherwise the
+    /// lowest numbered colour wins, which is only possible under rules with
+    /// births on counts other than three.
+    pub fn newborn(self, votes: [usize; 4]) -> u8 {
+        let votes = &votes[..self.colours()];
+        let most = votes.iter().copied().max().unwrap_or(0);
+        let tied = votes.iter().filter(|&&count| count == most).count();
+        if self == Colouring::QuadLife && most == 1 && tied == 3 {
+            if let Some(missing) = votes.iter().position(|&count| count == 0) {
+                return missing as u8 + 1;
+            }
+        }
+        votes.iter().position(|&count| count == most).unwrap_or(0) as u8 + 1
+    }
+}
+
+/// [Axis Neighbours]
+/// The coordinates one before, at, and one after `pos` along an axis
+/// `len` cells long, which wrap around the axis, or are either dropped
+/// or moved back onto the nearest edge, according to the boundary.
+///
+/// On axes only one or two cells long, wrapping around lands on a cell
+/// which is already counted, either `pos` itself or its neighbour on the
+/// other side. Such duplicates are dropped, so that no cell is ever
+/// counted twice on a torus.
+#[inline]
+fn axis_neighbours(len: usize, pos: usize, boundary: Boundary) -> [Option<usize>; 3] {
+    let before = if pos == 0 { None } else { Some(pos - 1) };
+    let after = if pos + 1 == len { None } else { Some(pos + 1) };
+
+    match boundary {
+        Boundary::Torus => {
+            let before = before.unwrap_or(len - 1);
+            let after = after.unwrap_or(0);
+            [Some(before).filter(|&n| n != pos), Some(pos), Some(after).filter(|&n| n != pos && n != before)]
+        },
+        Boundary::Dead => [before, Some(pos), after],
+        Boundary::Reflect => [before.or(Some(pos)), Some(pos), after.or(Some(pos))],
+    }
+}
+
+/// [For Each Around]
+/// Calls `f` with the index of every neighbour of a cell, on a board
+/// `cols` cells wide, given the columns and rows around it as found by
+/// `axis_neighbours`.
+#[inline]
+fn for_each_around<F: FnMut(usize)>(cols: usize, xs: [Option<usize>; 3], ys: [Option<usize>; 3],
+                                    neighbourhood: Neighbourhood, mut f: F) {
+    for (dy, ny) in ys.iter().enumerate() {
+        for (dx, nx) in xs.iter().enumerate() {
+            if (dx, dy) == (1, 1) {
+                continue;
+            }
+
+            // Diagonal neighbours are offset along both axes, and orthogonal ones along only one.
+            let diagonal = dx != 1 && dy != 1;
+            match neighbourhood {
+                Neighbourhood::VonNeumann if diagonal => continue,
+                Neighbourhood::Diagonal if !diagonal => continue,
+                _ => {}
+            }
+
+            if let (Some(nx), Some(ny)) = (nx, ny) {
+                f(nx + ny * cols);
+            }
+        }
+    }
+}
+
+/// [For Each Neighbour]
+/// Calls `f` with the index of every neighbour of the cell at index `i`,
+/// within the given neighbourhood, on a board `cols` cells wide and
+/// `rows` cells tall. Each neighbour
+/// is located by its column and row, which wrap around independently of
+/// one another, so that the board forms a true torus: a cell on the left
+/// edge sees the right edge of its own row, rather than the previous row.
+///
+/// With dead edges, cells beyond the edges of the board are not
+/// neighbours at all, and are skipped. With reflective edges, a cell
+/// beyond an edge is replaced by the nearest cell on it, so that cells
+/// along the edges count themselves or their edge neighbours twice.
+#[inline]
+pub(crate) fn for_each_neighbour<F: FnMut(usize)>(cols: usize, rows: usize, i: usize, boundary: Boundary,
+                                                  neighbourhood: Neighbourhood, f: F) {
+    let (x, y) = (i % cols, i / cols);
+    let (xs, ys) = (axis_neighbours(cols, x, boundary), axis_neighbours(rows, y, boundary));
+    for_each_around(cols, xs, ys, neighbourhood, f);
+}
+
+/// [Neighbours]
+/// Counts the living neighbours of the cell at index `i`, treating every
+/// cell beyond the edges of the board according to the boundary.
+fn neighbours(state: &Cells, cols: usize, rows: usize, i: usize, boundary: Boundary, neighbourhood: Neighbourhood) -> usize {
+    let mut neighbour = 0;
+    for_each_neighbour(cols, rows, i, boundary, neighbourhood, |n| neighbour += state.get(n) as usize);
+    neighbour
+}
+
+/// [Generation]
+/// Computes the generation following `previous_state` into `state`, for
+/// a board `cols` cells wide and `rows` cells tall, by checking each
+/// individual cell from the previous state, and updating the focused
+/// cell for the next state accordingly. This function has been
+/// parallelized using the Rayon crate, in order to allow each band of
+/// `BAND_ROWS` rows to be analyzed by the next available parallel
+/// thread. Each band reads only its own rows and the rows either side
+/// of it, and walks its cells in order, so that the rows around each
+/// cell are only located once per row.
+///
+/// When given `counts`, which must hold one entry per cell, the number of
+/// living neighbours each cell had in `previous_state` is written there
+/// as it is counted. While thread statistics are enabled, each band is
+/// counted towards the thread which computed it.
+#[allow(clippy::too_many_arguments)]
+pub fn generation(previous_state: &Cells, state: &mut Cells, cols: usize, rows: usize,
+                  rule: Rule, boundary: Boundary, neighbourhood: Neighbourhood, counts: Option<&mut [u8]>) {
+    use rayon::prelude::*;
+
+    let band_words = band_words(cols);
+
+    // Each band is computed alike, whether or not its counts are kept.
+    let size = previous_state.len();
+    let compute = |b: usize, band: &mut [u64], counts: Option<&mut [u8]>| {
+        let first = b * band_words * WORD_BITS;
+        stats::time_band((band.len() * WORD_BITS).min(size - first), || {
+            generation_band(previous_state, band, first, cols, rows, rule, boundary, neighbourhood, counts);
+        });
+    };
+
+    // Rayon parallel iterator:
+    // .enumerate() -> Provides us with an index for each iterated value.
+    //                 this is necessary for the Game of Life.
+    // .for_each()  -> Iterates over each value of the parallel iterator.
+    //                 Provides the index of the focused band, and a
+    //                 reference to the focused band's words within its
+    //                 closure (straight brackets), along with its counts.
+    let bands = state.words_mut().par_chunks_mut(band_words);
+    match counts {
+        Some(counts) => bands.zip(counts.par_chunks_mut(band_words * WORD_BITS))
+            .enumerate()
+            .for_each(|(b, (band, counts))| compute(b, band, Some(counts))),
+        None => bands.enumerate()
+            .for_each(|(b, band)| compute(b, band, None)),
+    }
+}
+
+/// [Sequential Generation]
+/// Computes the generation following `previous_state` into `state`
+/// exactly as `generation` does, band by band, but on the calling thread
+/// alone. Useful for measuring how much the parallel update gains.
+pub fn sequential_generation(previous_state: &Cells, state: &mut Cells, cols: usize, rows: usize,
+                             rule: Rule, boundary: Boundary, neighbourhood: Neighbourhood) {
+    let band_words = band_words(cols);
+    for (b, band) in state.words_mut().chunks_mut(band_words).enumerate() {
+        generation_band(previous_state, band, b * band_words * WORD_BITS, cols, rows, rule, boundary, neighbourhood, None);
+    }
+}
+
+/// [Asynchronous Generation]
+/// Updates every cell of `state` in place, one at a time in order from
+/// the upper left corner, for a board `cols` cells wide and `rows` cells
+/// tall. Unlike `generation`, there is no second buffer, so each cell
+/// counts the neighbours before it as they already are in the new
+/// generation, and those after it as they were in the previous one. This
+/// deliberately breaks the simultaneity of the classic update, and gives
+/// very different dynamics.
+///
+/// Since every cell depends on the one before it, the pass is inherently
+/// sequential, and cannot be parallelized.
+///
+/// When given `counts`, the number of living neighbours each cell saw
+/// when it was updated is written there.
+pub fn asynchronous_generation(state: &mut Cells, cols: usize, rows: usize, rule: Rule,
+                               boundary: Boundary, neighbourhood: Neighbourhood, mut counts: Option<&mut [u8]>) {
+    for i in 0..state.len() {
+        let neighbour = neighbours(state, cols, rows, i, boundary, neighbourhood);
+        if let Some(counts) = counts.as_mut() {
+            counts[i] = neighbour as u8;
+        }
+        let alive = rule.next(state.get(i), neighbour);
+        state.set(i, alive);
+    }
+}
+
+/// [Larger Than Life Generation]
+/// Computes the generation following `previous_state` into `state` under
+/// a Larger than Life rule, for a board `cols` cells wide and `rows` cells
+/// tall. Counting every cell within the rule's radius directly would cost
+/// the square of the neighbourhood's width per cell, so the counts are
+/// summed in two passes instead: first along each row, then down each
+/// column over those row sums. Both passes are parallelized with Rayon.
+/// This is still far slower than `generation` as the radius grows.
+///
+/// Cells beyond the edges are treated according to the boundary, as
+/// they are for `generation`. On a torus, the board must be at least
+/// twice the radius plus one cells across and down, so that no cell is
+/// counted twice.
+pub fn larger_than_life_generation(previous_state: &Cells, state: &mut Cells, cols: usize, rows: usize,
+                                   rule: LargerThanLife, boundary: Boundary) {
+    use rayon::prelude::*;
+
+    let size = previous_state.len();
+    let radius = rule.radius as isize;
+
+    // Locates the cell `offset` cells along an axis `len` cells long from `pos`.
+    let along = |len: usize, pos: usize, offset: isize| -> Option<usize> {
+        let moved = pos as isize + offset;
+        if moved >= 0 && moved < len as isize {
+            return Some(moved as usize);
+        }
+        match boundary {
+            Boundary::Torus => Some(moved.rem_euclid(len as isize) as usize),
+            Boundary::Dead => None,
+            Boundary::Reflect => Some(moved.clamp(0, len as isize - 1) as usize),
+        }
+    };
+
+    // Living cells within the radius of each cell along its own row.
+    let mut row_sums = vec![0u32; size];
+    row_sums.par_chunks_mut(cols).enumerate().for_each(|(y, sums)| {
+        for (x, sum) in sums.iter_mut().enumerate() {
+            *sum = (-radius..=radius)
+                .filter_map(|offset| along(cols, x, offset))
+                .map(|nx| previous_state.get(nx + y * cols) as u32)
+                .sum();
+        }
+    });
+
+    state.words_mut().par_iter_mut().enumerate().for_each(|(w, word)| {
+        let start = w * WORD_BITS;
+        let mut next = 0;
+        for bit in 0..WORD_BITS.min(size - start) {
+            let i = start + bit;
+            let (x, y) = (i % cols, i / cols);
+            let alive = previous_state.get(i);
+            let total: u32 = (-radius..=radius)
+                .filter_map(|offset| along(rows, y, offset))
+                .map(|ny| row_sums[x + ny * cols])
+                .sum();
+            let neighbours = total as usize - (alive && !rule.middle) as usize;
+            next |= (rule.next(alive, neighbours) as u64) << bit;
+        }
+        *word = next;
+    });
+}
+
+/// [Colour Generation]
+/// Updates `colours`, which holds the colour of every cell of
+/// `previous_state` (zero for dead cells, and from one for living ones),
+/// to those of `state`, the generation following it, on a board `cols`
+/// cells wide and `rows` cells tall. Surviving cells keep their colour,
+/// while newborn cells are coloured from the living neighbours they were
+/// born beside as the colouring decides, and dead cells have none. Living
+/// cells without a colour, such as those drawn in since, count as the
+/// first colour. Each cell is coloured independently, in parallel with Rayon.
+#[allow(clippy::too_many_arguments)]
+pub fn colour_generation(previous_state: &Cells, state: &Cells, colours: &mut [u8], cols: usize, rows: usize,
+                         boundary: Boundary, neighbourhood: Neighbourhood, colouring: Colouring) {
+    use rayon::prelude::*;
+
+    let previous = colours.to_vec();
+    colours.par_iter_mut().enumerate().for_each(|(i, colour)| {
+        *colour = if !state.get(i) {
+            0
+        } else if previous_state.get(i) {
+            previous[i].max(1)
+        } else {
+            let mut votes = [0; 4];
+            for_each_neighbour(cols, rows, i, boundary, neighbourhood, |n| {
+                if previous_state.get(n) {
+                    votes[previous[n].max(1) as usize - 1] += 1;
+                }
+            });
+            colouring.newborn(votes)
+        };
+    });
+}
+
+/// [Band Words]
+/// Number of words in each band of a board `cols` cells wide, as
+/// computed by `generation` and `sequential_generation`.
+fn band_words(cols: usize) -> usize {
+    // Enough rows per band to amortize scheduling, while leaving plenty of
+    // bands to spread across threads.
+    const BAND_ROWS: usize = 16;
+
+    (cols * BAND_ROWS).div_ceil(WORD_BITS).max(1)
+}
+
+/// [Generation Band]
+/// Computes one band of the generation following `previous_state` into
+/// its words, `band`, whose first cell lies at index `first`. Cells are
+/// walked in order, so that the rows around each cell are only located
+/// once per row. Counts, when given, hold one entry per cell of the band.
+#[allow(clippy::too_many_arguments)]
+#[inline]
+fn generation_band(previous_state: &Cells, band: &mut [u64], first: usize, cols: usize, rows: usize,
+                   rule: Rule, boundary: Boundary, neighbourhood: Neighbourhood, mut counts: Option<&mut [u8]>) {
+    let size = previous_state.len();
+    let (mut x, mut y) = (first % cols, first / cols);
+    let mut ys = axis_neighbours(rows, y, boundary);
+
+    for (w, word) in band.iter_mut().enumerate() {
+        let start = first + w * WORD_BITS;
+        let mut next = 0;
+
+        for bit in 0..WORD_BITS.min(size - start) {
+            let mut neighbour = 0;
+            let xs = axis_neighbours(cols, x, boundary);
+            for_each_around(cols, xs, ys, neighbourhood, |n| neighbour += previous_state.get(n) as usize);
+            if let Some(counts) = counts.as_mut() {
+                counts[start + bit - first] = neighbour as u8;
+            }
+
+            let alive = rule.next(previous_state.get(start + bit), neighbour);
+            next |= (alive as u64) << bit;
+
+            // Move along to the next cell, and onto the next row at the end of this one.
+            x += 1;
+            if x == cols {
+                x = 0;
+                y += 1;
+                if y < rows {
+                    ys = axis_neighbours(rows, y, boundary);
+                }
+            }
+        }
+
+        *word = next;
+    }
+}
+
+/// [Sparse Generation]
+/// Computes the generation following `previous_state` into `state`, just
+/// as `generation` does, but only evaluates the cells which are alive
+/// (as given by `live`) or neighbour a living cell, since no other cell
+/// can change. This is much faster on large, mostly empty boards. Returns
+/// the living cells of the new generation.
+///
+/// Rules under which cells are born without any living neighbours (B0)
+/// change every cell of the board, and must use `generation` instead.
+///
+/// When given `counts`, the number of living neighbours each cell had is
+/// written there just as `generation` does. Cells which were not
+/// evaluated had none.
+#[allow(clippy::too_many_arguments)]
+pub fn sparse_generation(previous_state: &Cells, live: &HashSet<usize>, state: &mut Cells,
+                         cols: usize, rows: usize, rule: Rule, boundary: Boundary,
+                         neighbourhood: Neighbourhood, counts: Option<&mut [u8]>) -> HashSet<usize> {
+    use rayon::prelude::*;
+
+    // Gather every cell which could possibly change.
+    let mut candidates: HashSet<usize> = HashSet::with_capacity(live.len() * 9);
+    for &i in live {
+        candidates.insert(i);
+        for_each_neighbour(cols, rows, i, boundary, neighbourhood, |n| { candidates.insert(n); });
+    }
+
+    // Observe state of neighbouring cells. Beyond the edges of
+    // the board, cells either wrap around, are simply dead, or mirror
+    // the edge.
+    let evaluated: Vec<(usize, usize)> = candidates.par_iter()
+        .map(|&i| (i, neighbours(previous_state, cols, rows, i, boundary, neighbourhood)))
+        .collect();
+    if let Some(counts) = counts {
+        counts.fill(0);
+        for &(i, neighbour) in &evaluated {
+            counts[i] = neighbour as u8;
+        }
+    }
+
+    // Based on current state, change to new state!
+    let next: HashSet<usize> = evaluated.par_iter()
+        .filter(|&&(i, neighbour)| rule.next(previous_state.get(i), neighbour))
+        .map(|&(i, _)| i)
+        .collect();
+
+    state.fill(false);
+    for &i in &next {
+        state.set(i, true);
+    }
+
+    next
+}
+
+/// [Word Window]
+/// The 64 cells starting at index `start`, packed into a word with cell
+/// `start` in its lowest bit. The window may start before the first
+/// cell or run past the last, in which case those cells count as dead.
+#[cfg(feature = "bitwise")]
+#[inline]
+fn word_window(words: &[u64], start: isize) -> u64 {
+    let (w, bit) = (start.div_euclid(WORD_BITS as isize), start.rem_euclid(WORD_BITS as isize) as u32);
+    let word = |w: isize| if w >= 0 && (w as usize) < words.len() { words[w as usize] } else { 0 };
+    if bit == 0 {
+        word(w)
+    } else {
+        word(w) >> bit | word(w + 1) << (WORD_BITS as u32 - bit)
+    }
+}
+
+/// [Bitwise Generation]
+/// Computes the generation following `previous_state` into `state`, just
+/// as `generation` does, but 64 cells at a time. Each neighbour of every
+/// cell in a word lies the same distance away along the packed board, so
+/// a word's neighbours are gathered as eight shifted windows, which are
+/// summed bit by bit into four words holding the binary digits of every
+/// cell's count. The rule is then applied to all 64 counts at once.
+///
+/// Shifting along the packed board runs straight off each row onto the
+/// next, so cells along the edges of the board are recomputed one at a
+/// time afterwards, by the same means as `generation`. Bands are counted
+/// towards thread statistics as they are there.
+#[cfg(feature = "bitwise")]
+pub fn bitwise_generation(previous_state: &Cells, state: &mut Cells, cols: usize, rows: usize,
+                          rule: Rule, boundary: Boundary, neighbourhood: Neighbourhood) {
+    use rayon::prelude::*;
+
+    // Enough words per band to amortize scheduling.
+    const BAND_WORDS: usize = 64;
+
+    let size = previous_state.len();
+    let words = previous_state.words();
+    let c = cols as isize;
+    let offsets: &[isize] = match neighbourhood {
+        Neighbourhood::Moore => &[-c - 1, -c, -c + 1, -1, 1, c - 1, c, c + 1],
+        Neighbourhood::VonNeumann => &[-c, -1, 1, c],
+        Neighbourhood::Diagonal => &[-c - 1, -c + 1, c - 1, c + 1],
+    };
+    let birth = rule.birth.map(|born| if born { !0u64 } else { 0 });
+    let survival = rule.survival.map(|survives| if survives { !0u64 } else { 0 });
+
+    state.words_mut().par_chunks_mut(BAND_WORDS)
+        .enumerate()
+        .for_each(|(b, band)| {
+            let cells = (band.len() * WORD_BITS).min(size - b * BAND_WORDS * WORD_BITS);
+            stats::time_band(cells, || {
+                for (w, word) in band.iter_mut().enumerate() {
+                    let index = b * BAND_WORDS + w;
+                    let start = (index * WORD_BITS) as isize;
+
+                    // Add each neighbour into the count's binary digits, carrying upwards.
+                    let (mut ones, mut twos, mut fours, mut eights) = (0u64, 0u64, 0u64, 0u64);
+                    for &offset in offsets {
+                        let neighbour = word_window(words, start + offset);
+                        let carry = ones & neighbour;
+                        ones ^= neighbour;
+                        let carry_twos = twos & carry;
+                        twos ^= carry;
+                        let carry_fours = fours & carry_twos;
+                        fours ^= carry_twos;
+                        eights |= carry_fours;
+                    }
+
+                    let alive = words[index];
+                    let mut next = 0;
+                    for count in 0..9 {
+                        let digit = |bits: u64, set: bool| if set { bits } else { !bits };
+                        let matches = digit(ones, count & 1 != 0) & digit(twos, count & 2 != 0)
+                            & digit(fours, count & 4 != 0) & digit(eights, count & 8 != 0);
+                        next |= matches & ((birth[count] & !alive) | (survival[count] & alive));
+                    }
+
+                    // Bits beyond the last cell must stay clear.
+                    let cells = WORD_BITS.min(size - index * WORD_BITS);
+                    *word = if cells == WORD_BITS { next } else { next & ((1 << cells) - 1) };
+                }
+
+                // Recompute this band's cells along the edges of the board.
+                let first = b * BAND_WORDS * WORD_BITS;
+                let end = (first + band.len() * WORD_BITS).min(size);
+                let mut fix = |i: usize| {
+                    if i >= first && i < end {
+                        let alive = rule.next(previous_state.get(i), neighbours(previous_state, cols, rows, i, boundary, neighbourhood));
+                        let (w, bit) = ((i - first) / WORD_BITS, (i - first) % WORD_BITS);
+                        band[w] = band[w] & !(1 << bit) | (alive as u64) << bit;
+                    }
+                };
+                for y in first / cols..=(end - 1) / cols {
+                    if y == 0 || y + 1 == rows {
+                        (0..cols).for_each(|x| fix(x + y * cols));
+                    } else {
+                        fix(y * cols);
+                        fix(y * cols + cols - 1);
+                    }
+                }
+            });
+        });
+}
+
+/// [Update Mode]
+/// How each generation is computed.
+///
+/// Variants:
+/// [Auto] Sparse while the board is mostly empty, and dense otherwise;
+/// [Dense] Every cell of the board is evaluated;
+/// [Sparse] Only living cells and their neighbours are evaluated.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum UpdateMode {
+    Auto,
+    Dense,
+    Sparse
+}
+
+impl UpdateMode {
+
+    /// [Is Sparse]
+    /// Whether the next generation of a board of `size` cells, with the
+    /// given population, should be computed sparsely. Rules with births
+    /// on zero neighbours are always computed densely.
+    pub fn is_sparse(self, population: usize, size: usize, rule: Rule) -> bool {
+        // Sparse updates only pay off once under one cell in this many lives.
+        const SPARSE_RATIO: usize = 32;
+
+        !rule.birth[0] && match self {
+            UpdateMode::Auto => population * SPARSE_RATIO < size,
+            UpdateMode::Dense => false,
+            UpdateMode::Sparse => true,
+        }
+    }
+}
+
+/// [Board]
+/// A game board, and everything needed to advance it a generation.
+///
+/// Fields:
+/// [cols] Width of the board, in cells;
+/// [rows] Height of the board, in cells;
+/// [state] State of the board as a flat array of bit-packed cells;
+/// [back] Back buffer, which the next generation is computed into;
+/// [rule] Birth and survival rule applied every generation;
+/// [boundary] What lies beyond the edges of the board;
+/// [neighbourhood] Which surrounding cells count as each cell's neighbours;
+/// [mode] How each generation is computed;
+/// [expanding] Whether the board grows whenever a living cell reaches one of its edges;
+/// [asynchronous] Whether cells are updated in place one at a time, rather than all at once;
+/// [larger_than_life] Larger than Life rule applied in place of `rule` and the neighbourhood, if any;
+/// [counts] Living neighbours of every cell in the previous generation, or since recounted, kept only when wanted;
+/// [colours] Colour of every cell, as kept by `colour_generation`, while a coloured variant is played;
+/// [colouring] How `colours` are given to newborn cells;
+/// [population] Number of living cells, as of the latest generation or refresh;
+/// [live] Index of every living cell, kept only while generations are computed sparsely.
+#[derive(Clone)]
+pub struct Board {
+    pub cols: usize,
+    pub rows: usize,
+    pub state: Cells,
+    pub back: Cells,
+    pub rule: Rule,
+    pub boundary: Boundary,
+    pub neighbourhood: Neighbourhood,
+    pub mode: UpdateMode,
+    pub expanding: bool,
+    pub asynchronous: bool,
+    pub larger_than_life: Option<LargerThanLife>,
+    pub counts: Option<Vec<u8>>,
+    pub colours: Option<Vec<u8>>,
+    pub colouring: Colouring,
+    pub population: usize,
+    live: HashSet<usize>
+}
+
+impl Board {
+
+    /// [New]
+    /// Creates a board `cols` cells wide and `rows` cells tall from its
+    /// initial state, which wraps around as a torus and uses the Moore
+    /// neighbourhood.
+    pub fn new(cols: usize, rows: usize, state: Cells, rule: Rule, mode: UpdateMode) -> Board {
+        let mut board = Board {
+            cols,
+            rows,
+            back: Cells::new(state.len()),
+            state,
+            rule,
+            boundary: Boundary::Torus,
+            neighbourhood: Neighbourhood::Moore,
+            mode,
+            expanding: false,
+            asynchronous: false,
+            larger_than_life: None,
+            counts: None,
+            colours: None,
+            colouring: Colouring::Immigration,
+            population: 0,
+            live: HashSet::new(),
+        };
+        board.refresh();
+        board
+    }
+
+    /// [Step]
+    /// Advances the board by exactly one generation, under the active
+    /// rule, boundary and neighbourhood settings.
+    ///
+    /// The next generation is written into the back buffer, while the
+    /// previous state is read from the front. This is necessary, as each
+    /// cell's update relies on the previous state of the board. Swapping
+    /// the two afterwards leaves the previous state in the back buffer,
+    /// without ever copying the board. Asynchronous updates work in place,
+    /// so copy the board into the back buffer beforehand instead, leaving
+    /// it just the same. In coloured variants, the colours
+    /// of the new generation are then found from both.
+    ///
+    /// An expanding board first grows to make room for any cells reaching
+    /// its edges, in which case the column and row at which the previous
+    /// board's upper left corner now lies are returned.
+    pub fn step(&mut self) -> Option<(usize, usize)> {
+        let grown = if self.expanding { self.expand() } else { None };
+
+        let asynchronous = self.asynchronous && self.larger_than_life.is_none();
+
+        let counts = self.counts.as_deref_mut();
+        if let Some(rule) = self.larger_than_life {
+            larger_than_life_generation(&self.state, &mut self.back, self.cols, self.rows, rule, self.boundary);
+            std::mem::swap(&mut self.state, &mut self.back);
+        } else if asynchronous {
+            // Updating in place overwrites the previous generation, so it is
+            // copied into the back buffer first, just as a swap would leave it.
+            self.back.clone_from(&self.state);
+            asynchronous_generation(&mut self.state, self.cols, self.rows,
+                                    self.rule, self.boundary, self.neighbourhood, counts);
+        } else {
+            if self.mode.is_sparse(self.population, self.state.len(), self.rule) {
+                self.live = sparse_generation(&self.state, &self.live, &mut self.back, self.cols, self.rows,
+                                              self.rule, self.boundary, self.neighbourhood, counts);
+            } else if cfg!(feature = "bitwise") && counts.is_none() {
+                #[cfg(feature = "bitwise")]
+                bitwise_generation(&self.state, &mut self.back, self.cols, self.rows,
+                                   self.rule, self.boundary, self.neighbourhood);
+            } else {
+                generation(&self.state, &mut self.back, self.cols, self.rows,
+                           self.rule, self.boundary, self.neighbourhood, counts);
+            }
+            std::mem::swap(&mut self.state, &mut self.back);
+        }
+
+        if let Some(colours) = self.colours.as_deref_mut() {
+            colour_generation(&self.back, &self.state, colours,
+                              self.cols, self.rows, self.boundary, self.neighbourhood, self.colouring);
+        }
+
+        self.refresh();
+        grown
+    }
+
+    /// [Expand]
+    /// Grows the board by `GROWTH` cells along each edge which a living
+    /// cell lies on, so that nothing is born beyond the board, though
+    /// never beyond `MAX_SIDE` cells in either dimension. Returns the
+    /// column and row at which the previous board's upper left corner now
+    /// lies, if the board grew.
+    ///
+    /// The board is a single flat grid rather than a set of tiles, so each
+    /// growth allocates a larger grid and copies every cell across, taking
+    /// time in proportion to the whole board rather than to the cells which
+    /// were added. Empty regions cost as much memory as busy ones, which at
+    /// `MAX_SIDE` comes to 2 MiB for each of the state and back buffers,
+    /// and 16 MiB more for colours, while the application's ages, fading
+    /// and RGBA pixel buffer, regrown alongside it, take another 112 MiB.
+    /// Its undo history and timeline each copy the board whole, so these
+    /// are shortened as it grows, to keep them within a fixed budget.
+    fn expand(&mut self) -> Option<(usize, usize)> {
+        let (cols, rows) = (self.cols, self.rows);
+        let alive = |x: usize, y: usize| self.state.get(x + y * cols);
+        let left = (0..rows).any(|y| alive(0, y));
+        let right = (0..rows).any(|y| alive(cols - 1, y));
+        let top = (0..cols).any(|x| alive(x, 0));
+        let bottom = (0..cols).any(|x| alive(x, rows - 1));
+
+        // Either side of each dimension shares whatever room is left.
+        let grow = |edge: bool, len: usize| if edge { GROWTH.min(MAX_SIDE.saturating_sub(len)) } else { 0 };
+        let add_left = grow(left, cols);
+        let add_right = grow(right, cols + add_left);
+        let add_top = grow(top, rows);
+        let add_bottom = grow(bottom, rows + add_top);
+        if add_left + add_right + add_top + add_bottom == 0 {
+            return None;
+        }
+
+        let new_cols = cols + add_left + add_right;
+        let new_rows = rows + add_top + add_bottom;
+        let moved = |i: usize| (i % cols + add_left) + (i / cols + add_top) * new_cols;
+        let mut state = Cells::new(new_cols * new_rows);
+        for i in self.state.alive_indices() {
+            state.set(moved(i), true);
+        }
+
+        // Living cells keep their colours as they move.
+        let colours = self.colours.take().map(|colours| {
+            let mut grown = vec![0; new_cols * new_rows];
+            for i in self.state.alive_indices() {
+                grown[moved(i)] = colours[i];
+            }
+            grown
+        });
+
+        self.replace(new_cols, new_rows, state);
+        self.colours = colours;
+        Some((add_left, add_top))
+    }
+
+    /// [Replace]
+    /// Replaces the board with another `cols` cells wide and `rows` cells
+    /// tall, keeping its settings.
+    pub fn replace(&mut self, cols: usize, rows: usize, state: Cells) {
+        self.cols = cols;
+        self.rows = rows;
+        self.back = Cells::new(state.len());
+        if self.counts.is_some() {
+            self.counts = Some(vec![0; state.len()]);
+        }
+        if self.colours.is_some() {
+            self.colours = Some(vec![0; state.len()]);
+        }
+        self.state = state;
+        self.refresh();
+    }
+
+    /// [Refresh]
+    /// Recounts the number of living cells, and gathers them for the next
+    /// generation should it be computed sparsely. Must be called whenever
+    /// the state is changed other than by `step`.
+    pub fn refresh(&mut self) {
+        self.population = self.state.count_alive();
+        if self.mode.is_sparse(self.population, self.state.len(), self.rule) {
+            self.live = self.state.alive_indices().collect();
+        } else {
+            self.live.clear();
+        }
+    }
+
+    /// [Recount]
+    /// Counts the living neighbours of every cell as the board now stands,
+    /// should counts be kept. Must be called whenever the state, boundary
+    /// or neighbourhood is changed other than by `step`, which gathers the
+    /// counts as it goes instead. Larger than Life rules keep no counts.
+    pub fn recount(&mut self) {
+        use rayon::prelude::*;
+
+        if let (Some(counts), None) = (self.counts.as_mut(), self.larger_than_life) {
+            let (state, cols, rows, boundary, neighbourhood) = (&self.state, self.cols, self.rows, self.boundary, self.neighbourhood);
+            counts.par_iter_mut().enumerate().for_each(|(i, count)| {
+                *count = neighbours(state, cols, rows, i, boundary, neighbourhood) as u8;
+            });
+        }
+    }
+
+    /// [Components]
+    /// Sizes of the separate clusters of living cells, largest first,
+    /// where two living cells belong to the same cluster whenever they
+    /// are neighbours under the board's neighbourhood and boundary. Found
+    /// by a breadth-first flood fill from each living cell not yet
+    /// reached.
+    pub fn components(&self) -> Vec<usize> {
+        let mut reached = vec![false; self.state.len()];
+        let mut sizes = Vec::new();
+        let mut queue = std::collections::VecDeque::new();
+
+        for start in self.state.alive_indices() {
+            if reached[start] {
+                continue;
+            }
+            reached[start] = true;
+            queue.push_back(start);
+
+            let mut size = 0;
+            while let Some(i) = queue.pop_front() {
+                size += 1;
+                for_each_neighbour(self.cols, self.rows, i, self.boundary, self.neighbourhood, |n| {
+                    if self.state.get(n) && !reached[n] {
+                        reached[n] = true;
+                        queue.push_back(n);
+                    }
+                });
+            }
+            sizes.push(size);
+        }
+
+        sizes.sort_unstable_by(|a, b| b.cmp(a));
+        sizes
+    }
+
+    /// [Centroid]
+    /// Average column and row of every living cell, or nothing if the
+    /// board is empty. Cells are measured from the board's upper left
+    /// corner to their own, so a single cell at the origin lies at (0, 0).
+    pub fn centroid(&self) -> Option<(f64, f64)> {
+        let (mut sum_x, mut sum_y, mut count) = (0.0, 0.0, 0usize);
+        for i in self.state.alive_indices() {
+            sum_x += (i % self.cols) as f64;
+            sum_y += (i / self.cols) as f64;
+            count += 1;
+        }
+        if count == 0 {
+            return None;
+        }
+        Some((sum_x / count as f64, sum_y / count as f64))
+    }
+
+    /// [Live Edges]
+    /// Whether any living cell lies along the left, top, right and bottom
+    /// edges of the board, in that order. Only the edges themselves are
+    /// checked, so this is cheap even on large boards.
+    pub fn live_edges(&self) -> [bool; 4] {
+        let (cols, rows) = (self.cols, self.rows);
+        let row_alive = |y: usize| (0..cols).any(|x| self.state.get(x + y * cols));
+        let column_alive = |x: usize| (0..rows).any(|y| self.state.get(x + y * cols));
+        [column_alive(0), row_alive(0), column_alive(cols - 1), row_alive(rows - 1)]
+    }
+
+    /// [Bounding Box]
+    /// Leftmost column, topmost row, rightmost column and bottom row
+    /// holding any living cell, or nothing if the board is empty.
+    pub fn bounding_box(&self) -> Option<(usize, usize, usize, usize)> {
+        self.state.alive_indices().fold(None, |bounds, i| {
+            let (x, y) = (i % self.cols, i / self.cols);
+            Some(match bounds {
+                None => (x, y, x, y),
+                Some((left, top, right, bottom)) => (left.min(x), top.min(y), right.max(x), bottom.max(y)),
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pattern::{self, Pattern};
+
+    /// Builds a Conway board with the given cells alive.
+    fn board_with(cols: usize, rows: usize, alive: &[(usize, usize)]) -> Board {
+        let mut state = Cells::new(cols * rows);
+        for &(x, y) in alive {
+            state.set(x + y * cols, true);
+        }
+        Board::new(cols, rows, state, Rule::conway(), UpdateMode::Dense)
+    }
+
+    /// Column and row of every living cell, in order.
+    fn alive(board: &Board) -> Vec<(usize, usize)> {
+        board.state.alive_indices().map(|i| (i % board.cols, i / board.cols)).collect()
+    }
+
+    /// A glider with its upper left corner at the given column and row,
+    /// wrapped around a board of the given size.
+    fn glider(cols: usize, rows: usize, left: usize, top: usize) -> Vec<(usize, usize)> {
+        let mut cells: Vec<(usize, usize)> = Pattern::from_offsets(pattern::GLIDER).cells.iter()
+            .map(|&(x, y)| ((x + left) % cols, (y + top) % rows))
+            .collect();
+        cells.sort_by_key(|&(x, y)| (y, x));
+        cells
+    }
+
+    #[test]
+    fn glider_crosses_the_torus_seam_intact() {
+        let mut board = board_with(16, 16, &glider(16, 16, 13, 5));
+        for _ in 0..12 {
+            board.step();
+        }
+        assert_eq!(alive(&board), glider(16, 16, 0, 8));
+    }
+
+    #[test]
+    fn sparse_and_dense_generations_agree() {
+        let cells = glider(40, 30, 3, 4);
+        let mut sparse = board_with(40, 30, &cells);
+        let mut dense = board_with(40, 30, &cells);
+        sparse.mode = UpdateMode::Sparse;
+        sparse.refresh();
+        for generation in 0..100 {
+            sparse.step();
+            dense.step();
+            assert!(sparse.state == dense.state, "The modes diverged at generation {}.", generation + 1);
+        }
+        assert_eq!(sparse.population, 5);
+    }
+
+    #[test]
+    fn still_lifes_are_unchanged() {
+        let block = [(2, 2), (3, 2), (2, 3), (3, 3)];
+        let beehive = [(3, 2), (4, 2), (2, 3), (5, 3), (3, 4), (4, 4)];
+        for cells in [&block[..], &beehive[..]] {
+            let mut board = board_with(8, 8, cells);
+            let before = board.state.clone();
+            board.step();
+            assert!(board.state == before);
+            assert_eq!(board.population, cells.len());
+        }
+    }
+
+    #[test]
+    fn larger_than_life_keeps_a_range_two_still_life() {
+        // Each cell of a 3x3 block sees all nine, itself included, while a
+        // dead cell beside it sees at most six.
+        let block: Vec<(usize, usize)> = (4..7).flat_map(|y| (4..7).map(move |x| (x, y))).collect();
+        let mut board = board_with(12, 12, &block);
+        board.larger_than_life = Some(LargerThanLife::parse("R2,C0,M1,S9..9,B7..25,NM").unwrap());
+        let before = board.state.clone();
+        for _ in 0..3 {
+            board.step();
+        }
+        assert!(board.state == before);
+        assert_eq!(board.population, 9);
+    }
+
+    #[test]
+    fn larger_than_life_at_radius_one_is_conway() {
+        let cells = glider(20, 20, 3, 4);
+        let mut conway = board_with(20, 20, &cells);
+        let mut ltl = board_with(20, 20, &cells);
+        ltl.larger_than_life = Some(LargerThanLife::parse("R1,C0,M0,S2..3,B3..3,NM").unwrap());
+        for _ in 0..8 {
+            conway.step();
+            ltl.step();
+            assert_eq!(alive(&ltl), alive(&conway));
+        }
+    }
+
+    #[test]
+    fn larger_than_life_parses_golly_notation() {
+        let bosco = LargerThanLife { radius: 5, middle: true, birth: (34, 45), survival: (34, 58) };
+        assert_eq!(LargerThanLife::parse("R5,C0,M1,S34..58,B34..45,NM"), Ok(bosco));
+        assert_eq!(LargerThanLife::parse(" r5, m1, s34..58, b34..45 "), Ok(bosco));
+        assert_eq!(LargerThanLife::parse(&bosco.to_string()), Ok(bosco));
+        assert_eq!(LargerThanLife::parse("R2,S1..2,B3..3").map(|rule| rule.middle), Ok(false));
+    }
+
+    #[test]
+    fn larger_than_life_rejects_malformed_rules() {
+        for rule in ["", "R5", "S34..58,B34..45", "R0,S1..2,B3..3", "Rx,S1..2,B3..3",
+                     "R2,S5..3,B3..3", "R2,S1..x,B3..3", "R2,S4,B3..3", "R2,C3,S1..2,B3..3",
+                     "R2,M2,S1..2,B3..3", "R2,S1..2,B3..3,NN", "R2,S1..2,B3..3,Q1"] {
+            assert!(LargerThanLife::parse(rule).is_err(), "{:?} parsed", rule);
+        }
+    }
+
+    #[test]
+    fn immigration_newborns_take_the_majority_colour() {
+        assert_eq!(Colouring::Immigration.newborn([2, 1, 0, 0]), 1);
+        assert_eq!(Colouring::Immigration.newborn([1, 2, 0, 0]), 2);
+        // Only the first two colours are counted.
+        assert_eq!(Colouring::Immigration.newborn([0, 1, 3, 0]), 2);
+        // A tie goes to the lowest numbered colour.
+        assert_eq!(Colouring::Immigration.newborn([1, 1, 0, 0]), 1);
+        assert_eq!(Colouring::Immigration.newborn([2, 2, 0, 0]), 1);
+    }
+
+    #[test]
+    fn quadlife_newborns_take_the_majority_or_missing_colour() {
+        assert_eq!(Colouring::QuadLife.newborn([0, 0, 2, 1]), 3);
+        assert_eq!(Colouring::QuadLife.newborn([0, 1, 0, 2]), 4);
+        // Three parents of different colours give the fourth.
+        assert_eq!(Colouring::QuadLife.newborn([1, 1, 1, 0]), 4);
+        assert_eq!(Colouring::QuadLife.newborn([0, 1, 1, 1]), 1);
+        assert_eq!(Colouring::QuadLife.newborn([1, 0, 1, 1]), 2);
+        // Any other tie goes to the lowest numbered colour.
+        assert_eq!(Colouring::QuadLife.newborn([1, 1, 1, 1]), 1);
+        assert_eq!(Colouring::QuadLife.newborn([0, 2, 2, 0]), 2);
+    }
+
+    #[test]
+    fn recount_counts_the_board_as_it_stands() {
+        let mut board = board_with(5, 5, &[(1, 2), (2, 2), (3, 2)]);
+        board.counts = Some(vec![0; 25]);
+        board.recount();
+        let counts = board.counts.as_ref().unwrap();
+        assert_eq!(counts[2 + 2 * 5], 2);
+        assert_eq!(counts[2 + 5], 3);
+        assert_eq!(counts[0], 0);
+    }
+
+    #[test]
+    fn corner_neighbours_depend_on_the_boundary() {
+        // The corner itself, its diagonal neighbour, and the opposite corner.
+        let board = board_with(4, 4, &[(0, 0), (1, 1), (3, 3)]);
+        let count = |boundary| neighbours(&board.state, 4, 4, 0, boundary, Neighbourhood::Moore);
+        assert_eq!(count(Boundary::Torus), 2);
+        assert_eq!(count(Boundary::Dead), 1);
+        // Beyond both edges, the corner reflects onto itself three times.
+        assert_eq!(count(Boundary::Reflect), 4);
+    }
+
+    #[test]
+    fn narrow_tori_count_each_neighbour_once() {
+        // On a full board, each cell sees every other cell within a step of it exactly once.
+        let full = |cols: usize, rows: usize| {
+            let mut state = Cells::new(cols * rows);
+            state.fill(true);
+            state
+        };
+        let count = |cols: usize, rows: usize, i: usize| neighbours(&full(cols, rows), cols, rows, i, Boundary::Torus, Neighbourhood::Moore);
+
+        assert_eq!(count(5, 1, 2), 2);
+        assert_eq!(count(5, 1, 0), 2);
+        assert_eq!(count(5, 2, 2), 5);
+        assert_eq!(count(5, 2, 5), 5);
+        assert_eq!(count(1, 1, 0), 0);
+        assert_eq!(count(2, 2, 3), 3);
+        assert_eq!(count(1, 5, 0), 2);
+        assert_eq!(count(2, 5, 9), 5);
+    }
+}
+
+#[cfg(all(test, feature = "bitwise"))]
+mod bitwise_tests {
+    use super::*;
+
+    #[test]
+    fn bitwise_generation_matches_generation() {
+        // A cheap xorshift fills the boards, so the test needs no dependencies.
+        let mut seed = 0x9e37_79b9_7f4a_7c15u64;
+        let mut random = move || {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed
+        };
+
+        for &(cols, rows) in &[(70, 33), (127, 10), (65, 2), (100, 1), (200, 64)] {
+            for &boundary in &[Boundary::Torus, Boundary::Dead, Boundary::Reflect] {
+                for &neighbourhood in &[Neighbourhood::Moore, Neighbourhood::VonNeumann, Neighbourhood::Diagonal] {
+                    let mut state = Cells::new(cols * rows);
+                    for i in 0..state.len() {
+                        state.set(i, random() % 3 == 0);
+                    }
+
+                    for rule in [Rule::conway(), Rule::parse("B36/S23").unwrap(), Rule::parse("B1/S012").unwrap()] {
+                        let (mut scalar, mut bitwise) = (Cells::new(state.len()), Cells::new(state.len()));
+                        generation(&state, &mut scalar, cols, rows, rule, boundary, neighbourhood, None);
+                        bitwise_generation(&state, &mut bitwise, cols, rows, rule, boundary, neighbourhood);
+                        assert!(scalar == bitwise, "The kernels differ on a {} x {} board under {} with {:?} edges and the {:?} neighbourhood.",
+                                cols, rows, rule, boundary, neighbourhood);
+                    }
+                }
+            }
+        }
+    }
+}
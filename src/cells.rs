@@ -0,0 +1,194 @@
+/*****************************************************************/
+//! [Cells]
+/*****************************************************************/
+//!
+//! Bit-packed storage for the state of the game board. Each cell
+//! takes up a single bit of a `u64` word, rather than a whole byte
+//! as a `bool` would, which shrinks the board eightfold and keeps
+//! far more of it in cache while counting neighbours.
+/*****************************************************************/
+
+/// Number of cells packed into each word.
+pub const WORD_BITS: usize = 64;
+
+/// [Cells]
+/// A fixed number of cells, each either alive or dead, packed into
+/// words. Any bits of the final word beyond the last cell are always
+/// kept clear, so that whole words may be counted or compared.
+///
+/// Fields:
+/// [words] The packed cells, with cell `i` stored in bit `i % 64` of word `i / 64`;
+/// [len] Number of cells.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Cells {
+    words: Vec<u64>,
+    len: usize
+}
+
+impl Cells {
+
+    /// [New]
+    /// Creates the given number of cells, all of them dead.
+    pub fn new(len: usize) -> Cells {
+        Cells { words: vec![0; len.div_ceil(WORD_BITS)], len }
+    }
+
+    /// [From Words]
+    /// Creates the given number of cells from their packed words, as given
+    /// by `words`. Gives nothing unless there are exactly as many words as
+    /// the cells need. Any bits beyond the last cell are cleared.
+    pub fn from_words(words: Vec<u64>, len: usize) -> Option<Cells> {
+        if words.len() != len.div_ceil(WORD_BITS) {
+            return None;
+        }
+        let mut cells = Cells { words, len };
+        cells.clear_padding();
+        Some(cells)
+    }
+
+    /// [Len]
+    /// Number of cells.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// [Is Empty]
+    /// Whether there are no cells at all.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// [Get]
+    /// Whether the cell at index `i` is alive.
+    #[inline]
+    pub fn get(&self, i: usize) -> bool {
+        debug_assert!(i < self.len);
+        self.words[i / WORD_BITS] >> (i % WORD_BITS) & 1 == 1
+    }
+
+    /// [Set]
+    /// Brings the cell at index `i` to life, or kills it.
+    #[inline]
+    pub fn set(&mut self, i: usize, alive: bool) {
+        debug_assert!(i < self.len);
+        let mask = 1 << (i % WORD_BITS);
+        if alive {
+            self.words[i / WORD_BITS] |= mask;
+        } else {
+            self.words[i / WORD_BITS] &= !mask;
+        }
+    }
+
+    /// [Fill]
+    /// Brings every cell to life, or kills every cell.
+    pub fn fill(&mut self, alive: bool) {
+        self.words.fill(if alive { !0 } else { 0 });
+        self.clear_padding();
+    }
+
+    /// [Invert]
+    /// Brings every dead cell to life, and kills every living cell. Whole
+    /// words are flipped at a time, in parallel with Rayon.
+    pub fn invert(&mut self) {
+        use rayon::prelude::*;
+
+        self.words.par_iter_mut().for_each(|word| *word = !*word);
+        self.clear_padding();
+    }
+
+    /// [Count Alive]
+    /// Number of living cells.
+    pub fn count_alive(&self) -> usize {
+        self.words.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    /// [Iter]
+    /// Iterates over every cell in order, yielding whether it is alive.
+    pub fn iter(&self) -> impl Iterator<Item = bool> + '_ {
+        (0..self.len).map(move |i| self.get(i))
+    }
+
+    /// [Alive Indices]
+    /// Iterates over the index of every living cell in order, skipping
+    /// whole words of dead cells at a time.
+    pub fn alive_indices(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(w, &word)| {
+            let mut bits = word;
+            std::iter::from_fn(move || {
+                if bits == 0 {
+                    return None;
+                }
+                let bit = bits.trailing_zeros() as usize;
+                bits &= bits - 1;
+                Some(w * WORD_BITS + bit)
+            })
+        })
+    }
+
+    /// [Words]
+    /// The packed words, for reading whole words at a time.
+    pub fn words(&self) -> &[u64] {
+        &self.words
+    }
+
+    /// [Words Mut]
+    /// The packed words, for writing whole words at a time. Callers must
+    /// leave the bits beyond the last cell clear.
+    pub fn words_mut(&mut self) -> &mut [u64] {
+        &mut self.words
+    }
+
+    /// [Clear Padding]
+    /// Clears the bits of the final word beyond the last cell.
+    fn clear_padding(&mut self) {
+        let used = self.len % WORD_BITS;
+        if used != 0 {
+            if let Some(last) = self.words.last_mut() {
+                *last &= (1 << used) - 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get_and_set_round_trip_across_word_boundaries() {
+        let mut cells = Cells::new(130);
+        for &i in &[63, 64, 65] {
+            cells.set(i, true);
+            assert!(cells.get(i));
+        }
+        assert_eq!(cells.count_alive(), 3);
+        assert!(!cells.get(62) && !cells.get(66));
+
+        cells.set(64, false);
+        assert!(cells.get(63) && !cells.get(64) && cells.get(65));
+        assert_eq!(cells.alive_indices().collect::<Vec<_>>(), vec![63, 65]);
+    }
+
+    #[test]
+    fn from_words_clears_padding() {
+        let cells = Cells::from_words(vec![!0, !0], 70).unwrap();
+        assert_eq!(cells.count_alive(), 70);
+        assert_eq!(cells.words()[1], (1 << 6) - 1);
+
+        assert!(Cells::from_words(vec![0], 70).is_none());
+        assert!(Cells::from_words(vec![0; 3], 70).is_none());
+    }
+
+    #[test]
+    fn invert_leaves_padding_clear() {
+        let mut cells = Cells::new(65);
+        cells.set(64, true);
+        cells.invert();
+        assert_eq!(cells.count_alive(), 64);
+        assert_eq!(cells.words()[1], 0);
+
+        cells.fill(true);
+        cells.invert();
+        assert_eq!(cells.count_alive(), 0);
+    }
+}
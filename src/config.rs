@@ -0,0 +1,86 @@
+/*****************************************************************/
+//! [Config]
+/*****************************************************************/
+//!
+//! Session settings saved to and loaded from a small TOML file, so
+//! that the same options need not be typed out for every run. Each
+//! setting is optional, and command-line flags override any given
+//! here. The board itself is never saved. The window's geometry is
+//! kept separately, and saved automatically on exit.
+/*****************************************************************/
+
+/// [Config]
+/// Settings for a session, as written in its TOML file.
+///
+/// Fields:
+/// [threads] Number of threads to run on, with zero meaning one per logical CPU;
+/// [density] Probability of each cell of a random board starting out alive;
+/// [rule] Rule in B/S notation, such as `B3/S23`;
+/// [boundary] What lies beyond the edges of the board, as `torus`, `dead` or `reflect`;
+/// [theme] Name of the colour theme the board is drawn in;
+/// [scale] Size of each cell, in pixels;
+/// [scale_x] Width of each cell, in pixels, when it differs from its height;
+/// [scale_y] Height of each cell, in pixels, when it differs from its width.
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub threads: Option<usize>,
+    pub density: Option<f64>,
+    pub rule: Option<String>,
+    pub boundary: Option<String>,
+    pub theme: Option<String>,
+    pub scale: Option<usize>,
+    pub scale_x: Option<usize>,
+    pub scale_y: Option<usize>
+}
+
+impl Config {
+
+    /// [Load]
+    /// Reads the settings from a TOML file.
+    pub fn load(path: &str) -> Result<Config, String> {
+        let text = std::fs::read_to_string(path).map_err(|e| format!("Could not read {}: {}", path, e))?;
+        toml::from_str(&text).map_err(|e| format!("Could not parse {}: {}", path, e))
+    }
+
+    /// [Save]
+    /// Writes the settings to a TOML file, replacing anything already
+    /// there.
+    pub fn save(&self, path: &str) -> Result<(), String> {
+        let text = toml::to_string(self).map_err(|e| format!("Could not encode the settings: {}", e))?;
+        std::fs::write(path, text).map_err(|e| format!("Could not write {}: {}", path, e))
+    }
+}
+
+/// [Geometry]
+/// Where the window was, and how large it was, when the game last exited.
+///
+/// Fields:
+/// [position] Position of the window's upper left corner on screen, if it could be found;
+/// [size] Width and height of the window, in pixels.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Geometry {
+    pub position: Option<[i32; 2]>,
+    pub size: [f64; 2]
+}
+
+impl Geometry {
+
+    /// [Load]
+    /// Reads the geometry from a TOML file. A missing or malformed file,
+    /// or one holding a size no window could have, gives nothing.
+    pub fn load(path: &str) -> Option<Geometry> {
+        let text = std::fs::read_to_string(path).ok()?;
+        toml::from_str::<Geometry>(&text).ok()
+            .filter(|geometry| geometry.size.iter().all(|&side| side.is_finite() && side >= 1.0))
+    }
+
+    /// [Save]
+    /// Writes the geometry to a TOML file, replacing anything already
+    /// there.
+    pub fn save(&self, path: &str) -> Result<(), String> {
+        let text = toml::to_string(self).map_err(|e| format!("Could not encode the window geometry: {}", e))?;
+        std::fs::write(path, text).map_err(|e| format!("Could not write {}: {}", path, e))
+    }
+}
@@ -0,0 +1,64 @@
+/*****************************************************************/
+//! [Game of Life]
+/*****************************************************************/
+//!
+//! The simulation behind the Game of Life, usable from other Rust code
+//! without any windowing or graphics. The executable is built on top
+//! of this library, adding rendering and input with Piston.
+//!
+//! The `step` function advances a plain slice of cells under Conway's
+//! rule, while the `board` module offers the full simulation, with
+//! bit-packed cells, other rules, boundaries and neighbourhoods.
+//! `configure_threads` sizes the thread pool every parallel update runs on.
+/*****************************************************************/
+
+// Define external libraries.
+extern crate rayon;
+
+pub mod board;
+pub mod cells;
+pub mod pattern;
+pub mod stats;
+
+use board::{Boundary, Neighbourhood, Rule};
+
+/// [Configure Threads]
+/// Sizes Rayon's global thread pool to the given number of threads, or
+/// leaves it at one thread per logical CPU for zero, and returns how many
+/// threads it ended up with. The global pool can only be built once per
+/// process, so should it already have been, the existing pool is kept
+/// with a warning rather than a panic.
+pub fn configure_threads(threads: usize) -> usize {
+    if threads > 0 {
+        if let Err(e) = rayon::ThreadPoolBuilder::new().num_threads(threads).build_global() {
+            println!("Warning: could not use {} threads ({}), keeping {}.", threads, e, rayon::current_num_threads());
+        }
+    }
+    rayon::current_num_threads()
+}
+
+/// [Step]
+/// Computes the generation following `current` into `next`, under
+/// Conway's rule, for a board `rows` cells tall and `cols` cells wide
+/// stored row by row. When `wrap` is true, the board wraps around as a
+/// torus, and otherwise every cell beyond its edges is dead. Each cell
+/// is computed in parallel with Rayon.
+///
+/// Panics if either slice does not hold exactly `rows * cols` cells.
+pub fn step(current: &[bool], next: &mut [bool], rows: usize, cols: usize, wrap: bool) {
+    use rayon::prelude::*;
+
+    assert_eq!(current.len(), rows * cols, "The current state must hold rows * cols cells.");
+    assert_eq!(next.len(), rows * cols, "The next state must hold rows * cols cells.");
+
+    let rule = Rule::conway();
+    let boundary = if wrap { Boundary::Torus } else { Boundary::Dead };
+    next.par_iter_mut()
+        .enumerate()
+        .for_each(|(i, cell)| {
+            let mut neighbours = 0;
+            board::for_each_neighbour(cols, rows, i, boundary, Neighbourhood::Moore,
+                                      |n| neighbours += current[n] as usize);
+            *cell = rule.next(current[i], neighbours);
+        });
+}
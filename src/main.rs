@@ -8,10 +8,10 @@
 //! Rust iterators.
 //!
 //! All graphics are generated using OpenGL with help from
-//! Rust's Piston API. Currently, each individual pixel is rendered
-//! as an OpenGL shape. There would be much more noticeable
-//! performance gains if this limitation were to be overcome,
-//! however this was not ameliorated due to time constraints.
+//! Rust's Piston API. The board is written into a pixel buffer
+//! holding one pixel per cell, which is uploaded to OpenGL as a
+//! single texture every frame, so that render time does not depend
+//! on the number of living cells.
 //!
 //! [Authors]
 //! Aiden Manuel (Original programming and idea),
@@ -31,174 +31,2592 @@ extern crate rand;
 extern crate chrono;
 extern crate rayon;
 extern crate conv;
+extern crate image;
+extern crate gif;
+#[macro_use]
+extern crate serde;
+extern crate toml;
+extern crate bincode;
+extern crate game_of_life;
 
 // Import necessary functions from external libraries.
 use graphics::*;
 use glutin_window::GlutinWindow as Window;
-use opengl_graphics::{GlGraphics, OpenGL};
+use opengl_graphics::{CreateTexture, Filter, Format, GlGraphics, GlyphCache, OpenGL, Texture, TextureSettings, UpdateTexture};
 use piston::event_loop::{EventSettings, Events};
 use piston::input::{RenderArgs, RenderEvent, UpdateArgs, UpdateEvent};
-use piston::window::WindowSettings;
+use piston::window::{AdvancedWindow, Window as _, WindowSettings};
 use piston::GenericEvent;
-use std::time::{Instant};
+use std::collections::{HashSet, VecDeque};
+use std::hash::{Hash, Hasher};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::time::{Duration, Instant};
+mod config;
+mod session;
 
-// Window dimensions (in pixels), as well as
-// visible scale-factor and other metrics.
+use config::{Config, Geometry};
+use session::Session;
+use game_of_life::board::{self, Board, Boundary, Colouring, LargerThanLife, Neighbourhood, Rule, UpdateMode};
+use game_of_life::cells::Cells;
+use game_of_life::pattern::{self, Pattern};
+use game_of_life::stats;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+// Default window dimensions (in pixels), as well as
+// visible scale-factor. All of these may be overridden
+// from the command line.
 const HEIGHT: usize = 1080;
 const WIDTH: usize = 1920;
 const SCALE: usize = 4;
-const ROWS: usize = HEIGHT / SCALE;
-const COLS: usize = WIDTH / SCALE;
-const SIZE: usize = (ROWS) * (COLS);
 
+// File the window's position and size are kept in between runs.
+const GEOMETRY_FILE: &str = ".game-of-life-window.toml";
+
+// Font used for all on-screen text, bundled into the executable.
+const FONT: &[u8] = include_bytes!("../assets/DejaVuSansMono.ttf");
+
+// Colour schemes the board may be drawn in, the first being the default.
+const THEMES: [Theme; 4] = [
+    Theme { name: "Classic", background: [0.9, 0.9, 0.85, 1.0], foreground: [0.6, 0.5, 0.52, 1.0] },
+    Theme { name: "Inverted", background: [0.6, 0.5, 0.52, 1.0], foreground: [0.9, 0.9, 0.85, 1.0] },
+    Theme { name: "High Contrast", background: [1.0, 1.0, 1.0, 1.0], foreground: [0.0, 0.0, 0.0, 1.0] },
+    Theme { name: "Terminal", background: [0.0, 0.0, 0.0, 1.0], foreground: [0.2, 1.0, 0.3, 1.0] },
+];
+
+// Tints cells are blended towards by their number of living neighbours, from
+// one to eight, while the neighbour count overlay is shown.
+const NEIGHBOUR_TINTS: [[f32; 4]; 8] = [
+    [0.45, 0.25, 0.65, 1.0],
+    [0.2, 0.4, 0.9, 1.0],
+    [0.1, 0.75, 0.3, 1.0],
+    [0.95, 0.85, 0.2, 1.0],
+    [1.0, 0.6, 0.1, 1.0],
+    [0.9, 0.3, 0.1, 1.0],
+    [0.75, 0.1, 0.3, 1.0],
+    [0.45, 0.0, 0.2, 1.0],
+];
+
+// Colours of the kinds of living cell in coloured variants, the first two
+// being those of Immigration, and all four those of QuadLife.
+const IMMIGRANTS: [[f32; 4]; 4] = [
+    [0.85, 0.25, 0.2, 1.0],
+    [0.2, 0.35, 0.85, 1.0],
+    [0.2, 0.7, 0.3, 1.0],
+    [0.9, 0.75, 0.1, 1.0],
+];
+
+// Number of frames a dying cell takes to fade away.
+const FADE_FRAMES: u8 = 3;
+
+// Longest period of oscillation told apart once a board settles down.
+const MAX_PERIOD: usize = 15;
+
+// Number of recent generations whose populations are graphed.
+const GRAPH_LEN: usize = 300;
+
+// Greatest number of changes to the board which can be undone.
+const MAX_UNDO: usize = 50;
+
+// Bytes the snapshots of each of the undo history and the timeline may
+// take up, which only shortens them once a board has grown large.
+const SNAPSHOT_BUDGET: usize = 256 << 20;
+
+/// [Theme]
+/// A colour scheme for the board.
+///
+/// Fields:
+/// [name] Name of the theme, as printed when it is chosen;
+/// [background] Colour of dead cells;
+/// [foreground] Colour of living cells.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Theme {
+    name: &'static str,
+    background: [f32; 4],
+    foreground: [f32; 4]
+}
+
+/// [Cell Shape]
+/// How each living cell is drawn within its square on the board. This is
+/// purely cosmetic, and plays no part in the simulation.
+///
+/// Variants:
+/// [Square] Filling the whole square;
+/// [Circle] As a circle inscribed in the square;
+/// [Rounded] As a square with rounded corners.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum CellShape {
+    Square,
+    Circle,
+    Rounded
+}
+
+impl CellShape {
+
+    /// [Next]
+    /// The shape following this one, in the order they are cycled through.
+    fn next(self) -> CellShape {
+        match self {
+            CellShape::Square => CellShape::Circle,
+            CellShape::Circle => CellShape::Rounded,
+            CellShape::Rounded => CellShape::Square,
+        }
+    }
+}
+
+impl std::str::FromStr for CellShape {
+    type Err = String;
+
+    /// [From Str]
+    /// Parses the name of a cell shape, which is case-insensitive.
+    fn from_str(shape: &str) -> Result<CellShape, String> {
+        match shape.to_ascii_lowercase().as_str() {
+            "square" => Ok(CellShape::Square),
+            "circle" => Ok(CellShape::Circle),
+            "rounded" => Ok(CellShape::Rounded),
+            _ => Err(format!("The cell shape \"{}\" must be one of square, circle or rounded.", shape)),
+        }
+    }
+}
+
+/// [Blend]
+/// How the cells of a stamped pattern combine with the cells already on
+/// the board beneath it.
+///
+/// Variants:
+/// [Or] Living cells of the pattern are brought to life, and every other cell is left alone;
+/// [Xor] Living cells of the pattern toggle the cells beneath them;
+/// [Replace] Every cell within the pattern's bounds is overwritten by the pattern, dead cells included.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Blend {
+    Or,
+    Xor,
+    Replace
+}
+
+impl Blend {
+
+    /// [Next]
+    /// The blend following this one, in the order they are cycled through.
+    fn next(self) -> Blend {
+        match self {
+            Blend::Or => Blend::Xor,
+            Blend::Xor => Blend::Replace,
+            Blend::Replace => Blend::Or,
+        }
+    }
+
+    /// [Apply]
+    /// Whether a cell within the pattern's bounds is alive once stamped,
+    /// given whether it was alive beforehand, and whether the pattern's
+    /// cell over it is.
+    fn apply(self, existing: bool, stamped: bool) -> bool {
+        match self {
+            Blend::Or => existing || stamped,
+            Blend::Xor => existing != stamped,
+            Blend::Replace => stamped,
+        }
+    }
+}
+
+/// [To RGBA8]
+/// Converts a floating point colour, as used by the graphics crate,
+/// into the four bytes of an RGBA pixel.
+fn to_rgba8(colour: [f32; 4]) -> [u8; 4] {
+    colour.map(|channel| (channel * 255.0).round() as u8)
+}
+
+/// [Encode SVG]
+/// Encodes living cells as an SVG image, drawing each one, given as its
+/// column, row and colour, as a `<rect>` one unit square at its place on
+/// the board, upon a background filling the view box. The view box covers
+/// only the region of the board given by its leftmost column, topmost row,
+/// width and height, in cells, and is drawn `scale` pixels per cell.
+fn encode_svg(cells: &[(usize, usize, [f32; 4])], region: (usize, usize, usize, usize), scale: [usize; 2], background: [f32; 4]) -> String {
+    let hex = |colour: [f32; 4]| {
+        let [r, g, b, _] = to_rgba8(colour);
+        format!("#{:02x}{:02x}{:02x}", r, g, b)
+    };
+
+    let (left, top, width, height) = region;
+    let mut svg = format!("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"{} {} {} {}\" shape-rendering=\"crispEdges\">\n",
+                          width * scale[0], height * scale[1], left, top, width, height);
+    svg.push_str(&format!("<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>\n", left, top, width, height, hex(background)));
+    for &(x, y, colour) in cells {
+        svg.push_str(&format!("<rect x=\"{}\" y=\"{}\" width=\"1\" height=\"1\" fill=\"{}\"/>\n", x, y, hex(colour)));
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// [Board Texture]
+/// Creates a texture holding one pixel per cell of a board `cols` cells
+/// wide and `rows` cells tall, from its RGBA pixel buffer. Pixels are
+/// sampled with the given filter, where `Filter::Nearest` keeps the edges
+/// of cells sharp and `Filter::Linear` smooths them.
+fn board_texture(pixels: &[u8], cols: usize, rows: usize, filter: Filter) -> Result<Texture, String> {
+    let texture_settings = TextureSettings::new().filter(filter).convert_gamma(true);
+    Texture::create(&mut (), Format::Rgba8, pixels, [cols as u32, rows as u32], &texture_settings)
+}
+
+/// [Board Transform]
+/// Derives the transformation from board coordinates, measured in cells,
+/// to the window, given the board's pan offset and the size of each cell.
+fn board_transform(transform: math::Matrix2d, offset: [f64; 2], scale: [f64; 2]) -> math::Matrix2d {
+    transform.trans(offset[0], offset[1]).scale(scale[0], scale[1])
+}
+
+/// [Age Colour]
+/// Maps the age of a living cell onto a gradient, from red for newly
+/// born cells to blue for cells which have survived `OLD_AGE` or more
+/// generations.
+fn age_colour(age: u16) -> [f32; 4] {
+    const OLD_AGE: u16 = 64;
+    const YOUNG: [f32; 4] = [0.85, 0.25, 0.25, 1.0];
+    const OLD: [f32; 4] = [0.25, 0.35, 0.85, 1.0];
+
+    mix(YOUNG, OLD, age.min(OLD_AGE) as f32 / OLD_AGE as f32)
+}
+
+/// [Mix]
+/// Blends linearly between two colours, from `from` at `t = 0` to `to`
+/// at `t = 1`.
+fn mix(from: [f32; 4], to: [f32; 4], t: f32) -> [f32; 4] {
+    [0, 1, 2, 3].map(|i| from[i] + (to[i] - from[i]) * t)
+}
+
+/// [Bench]
+/// Runs the given number of generations on a board without any graphics,
+/// then prints the total and average time taken per generation, along
+/// with the final population. Useful for comparing thread counts without
+/// any rendering noise.
+fn bench(generations: u64, mut board: Board) {
+    let time_initial = Instant::now();
+    for _ in 0..generations {
+        board.step();
+    }
+    let elapsed = time_initial.elapsed();
+
+    println!("Ran {} generations of a {} x {} board in {:.3}s", generations, board.cols, board.rows, elapsed.as_secs_f64());
+    println!("Average of {:.3}ms per generation", elapsed.as_secs_f64() * 1000.0 / generations.max(1) as f64);
+    println!("Final population: {}", board.population);
+    if stats::enabled() {
+        print_thread_stats();
+    }
+}
+
+/// [Print Thread Stats]
+/// Prints a table of the work done by each of Rayon's threads since the
+/// totals were last collected, along with each thread's share of the
+/// cells computed, and starts the totals over. Nothing is printed should
+/// no dense generation have been computed since.
+fn print_thread_stats() {
+    let work = stats::collect();
+    let total: u64 = work.iter().map(|work| work.cells).sum();
+    if total == 0 {
+        return;
+    }
+
+    println!("Thread    Bands       Cells  Busy (ms)   Share");
+    for (thread, work) in work.iter().enumerate() {
+        println!("{:>6} {:>8} {:>11} {:>10.3} {:>6.1}%", thread, work.bands, work.cells,
+                 work.busy.as_secs_f64() * 1000.0, 100.0 * work.cells as f64 / total as f64);
+    }
+}
+
+/// [Verify]
+/// Runs the given number of generations on copies of a board within
+/// thread pools of several sizes, and checks that every copy finishes in
+/// exactly the same state. Each generation only ever reads the previous
+/// one, so any difference would mean a data race. Exits with status 1
+/// should the results diverge.
+fn verify(generations: u64, board: Board) {
+    const THREAD_COUNTS: [usize; 5] = [1, 2, 3, 8, 16];
+
+    let mut reference: Option<(usize, Cells)> = None;
+    for &threads in &THREAD_COUNTS {
+        let pool = rayon::ThreadPoolBuilder::new().num_threads(threads).build()
+            .expect("Could not build a thread pool!");
+        let mut board = board.clone();
+        pool.install(|| {
+            for _ in 0..generations {
+                board.step();
+            }
+        });
+
+        match reference {
+            None => {
+                println!("{} threads: final population {}", threads, board.population);
+                reference = Some((threads, board.state));
+            },
+            Some((_, ref state)) if *state == board.state => {
+                println!("{} threads: final population {}, identical", threads, board.population);
+            },
+            Some((reference_threads, _)) => {
+                println!("{} threads: final population {}, differing from {} threads!", threads, board.population, reference_threads);
+                std::process::exit(1);
+            },
+        }
+    }
+    println!("Every thread count agreed after {} generations.", generations);
+
+    // The bitwise kernel is checked against the scalar one it stands in for.
+    #[cfg(feature = "bitwise")]
+    {
+        if board.expanding || board.asynchronous || board.larger_than_life.is_some() {
+            println!("Expanding, asynchronous and Larger than Life boards are not compared against the scalar kernel.");
+            return;
+        }
+
+        let (mut previous, mut state) = (board.state.clone(), Cells::new(board.state.len()));
+        for _ in 0..generations {
+            game_of_life::board::generation(&previous, &mut state, board.cols, board.rows,
+                                            board.rule, board.boundary, board.neighbourhood, None);
+            std::mem::swap(&mut previous, &mut state);
+        }
+        match reference {
+            Some((_, ref reference)) if *reference == previous => println!("The scalar kernel agreed as well."),
+            _ => {
+                println!("The scalar kernel differed, with a final population of {}!", previous.count_alive());
+                std::process::exit(1);
+            },
+        }
+    }
+}
+
+/// [Soup Search]
+/// Runs `soups` random soups headlessly, in the manner of apgsearch. Each
+/// one fills a `side` by `side` square at the centre of an otherwise
+/// empty board at the given density, from its own seed counting up from
+/// `seed`, then runs until it settles into a still life or an oscillator
+/// of at most `MAX_PERIOD` generations, or until `max_generations` have
+/// passed. A CSV line is printed for each soup, holding its seed, how many
+/// generations it ran, the period of its ash (empty if it never settled),
+/// and the ash's population and bounding box.
+fn soup_search(soups: u64, seed: u64, density: f64, side: usize, max_generations: u64, mut board: Board) {
+    let (cols, rows) = (board.cols, board.rows);
+    let (side_x, side_y) = (side.min(cols), side.min(rows));
+    let (left, top) = ((cols - side_x) / 2, (rows - side_y) / 2);
+
+    println!("seed,generations,period,population,left,top,width,height");
+    let mut history = CycleHistory::default();
+    for seed in seed..seed.saturating_add(soups) {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut state = Cells::new(cols * rows);
+        for y in top..top + side_y {
+            for x in left..left + side_x {
+                state.set(x + y * cols, rng.gen_bool(density));
+            }
+        }
+        board.replace(cols, rows, state);
+
+        history.clear();
+        history.observe(&board.state);
+        let (mut generations, mut period) = (0, None);
+        while period.is_none() && generations < max_generations {
+            board.step();
+            generations += 1;
+            period = history.observe(&board.state);
+        }
+
+        let period = period.map_or(String::new(), |period| period.to_string());
+        match board.bounding_box() {
+            Some((left, top, right, bottom)) => println!("{},{},{},{},{},{},{},{}", seed, generations, period, board.population,
+                                                         left, top, right - left + 1, bottom - top + 1),
+            None => println!("{},{},{},0,,,0,0", seed, generations, period),
+        }
+    }
+}
+
+/// [Compare Bench]
+/// Runs the given number of dense generations on copies of a board twice,
+/// once on the calling thread alone and once in parallel with Rayon,
+/// then prints both times and the speedup between them. Exits with
+/// status 1 should the two finish in different states.
+fn compare_bench(generations: u64, board: Board) {
+    use game_of_life::board::{generation, sequential_generation};
+
+    let run = |parallel: bool| {
+        let (mut previous, mut state) = (board.state.clone(), Cells::new(board.state.len()));
+        let time_initial = Instant::now();
+        for _ in 0..generations {
+            if parallel {
+                generation(&previous, &mut state, board.cols, board.rows, board.rule, board.boundary, board.neighbourhood, None);
+            } else {
+                sequential_generation(&previous, &mut state, board.cols, board.rows, board.rule, board.boundary, board.neighbourhood);
+            }
+            std::mem::swap(&mut previous, &mut state);
+        }
+        (time_initial.elapsed(), previous)
+    };
+    let (sequential_time, sequential_state) = run(false);
+    let (parallel_time, parallel_state) = run(true);
+
+    println!("Ran {} generations of a {} x {} board", generations, board.cols, board.rows);
+    println!("Sequential: {:.3}s, {:.3}ms per generation", sequential_time.as_secs_f64(),
+             sequential_time.as_secs_f64() * 1000.0 / generations.max(1) as f64);
+    println!("Parallel ({} threads): {:.3}s, {:.3}ms per generation", rayon::current_num_threads(), parallel_time.as_secs_f64(),
+             parallel_time.as_secs_f64() * 1000.0 / generations.max(1) as f64);
+    println!("Speedup: {:.2}x", sequential_time.as_secs_f64() / parallel_time.as_secs_f64().max(f64::MIN_POSITIVE));
+
+    if sequential_state != parallel_state {
+        println!("The sequential and parallel updates differed, with final populations of {} and {}!",
+                 sequential_state.count_alive(), parallel_state.count_alive());
+        std::process::exit(1);
+    }
+    println!("Final population: {}, identical for both", parallel_state.count_alive());
+}
+
+/// [Record GIF]
+/// Runs the given number of generations on a board without any graphics,
+/// encoding each one as a frame of a looping animated GIF with cells
+/// `scale` pixels wide and tall, shown for `delay` milliseconds. Frames use a palette
+/// of only the two classic colours, which keeps files tiny.
+fn record_gif(path: &str, frames: u64, delay: u16, scale: [usize; 2], mut board: Board) -> Result<(), String> {
+    let (width, height) = (board.cols * scale[0], board.rows * scale[1]);
+    if width > u16::MAX as usize || height > u16::MAX as usize {
+        return Err(format!("A {} x {} GIF is too large for the format.", width, height));
+    }
+
+    let palette: Vec<u8> = [THEMES[0].background, THEMES[0].foreground].iter()
+        .flat_map(|&colour| to_rgba8(colour)[..3].to_vec())
+        .collect();
+    let file = File::create(path).map_err(|e| format!("Could not create {}: {}", path, e))?;
+    let mut encoder = gif::Encoder::new(BufWriter::new(file), width as u16, height as u16, &palette)
+        .map_err(|e| format!("Could not write {}: {}", path, e))?;
+    encoder.set_repeat(gif::Repeat::Infinite).map_err(|e| format!("Could not write {}: {}", path, e))?;
+
+    for frame_number in 0..frames {
+        if frame_number > 0 {
+            board.step();
+        }
+
+        // Each cell is scaled up into a block of identical palette indices.
+        let buffer: Vec<u8> = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x / scale[0], y / scale[1])))
+            .map(|(x, y)| board.state.get(x + y * board.cols) as u8)
+            .collect();
+        let mut frame = gif::Frame::from_indexed_pixels(width as u16, height as u16, buffer, None);
+        // GIF delays are counted in hundredths of a second.
+        frame.delay = delay.div_ceil(10);
+        encoder.write_frame(&frame).map_err(|e| format!("Could not write {}: {}", path, e))?;
+    }
+
+    println!("Recorded {} generations to {}", frames, path);
+    Ok(())
+}
+
+/// [Stamp]
+/// Writes the living cells of a pattern into a state array `cols` cells
+/// wide and `rows` cells tall, with the
+/// pattern's upper left corner placed at the given column and row, and
+/// combined with the cells beneath it by the given blend. Cells
+/// falling outside the board are clipped rather than wrapped around, and
+/// the number of living cells clipped is returned. Given the colours of
+/// a coloured variant, cells the pattern brings to life take the first
+/// colour, and those it kills lose theirs.
+#[allow(clippy::too_many_arguments)]
+fn stamp(state: &mut Cells, mut colours: Option<&mut [u8]>, cols: usize, rows: usize,
+         pattern: &Pattern, left: isize, top: isize, blend: Blend) -> usize {
+    let on_board = |x: isize, y: isize| (x >= 0 && (x as usize) < cols && y >= 0 && (y as usize) < rows)
+        .then(|| x as usize + y as usize * cols);
+
+    // Replacing clears the pattern's bounds before its living cells are
+    // drawn, though only as far as they overlap the board.
+    if blend == Blend::Replace {
+        let overlap = |start: isize, len: usize, bound: usize| {
+            let clamp = |edge: isize| edge.clamp(0, bound as isize) as usize;
+            clamp(start)..clamp(start.saturating_add(len as isize))
+        };
+        for y in overlap(top, pattern.height, rows) {
+            for x in overlap(left, pattern.width, cols) {
+                let i = x + y * cols;
+                state.set(i, false);
+                if let Some(colours) = colours.as_mut() {
+                    colours[i] = 0;
+                }
+            }
+        }
+    }
+
+    let mut clipped = 0;
+    for &(x, y) in &pattern.cells {
+        match on_board(left + x as isize, top + y as isize) {
+            Some(i) => {
+                let (was_alive, alive) = (state.get(i), blend.apply(state.get(i), true));
+                state.set(i, alive);
+                if let Some(colours) = colours.as_mut() {
+                    colours[i] = if !alive { 0 } else if was_alive { colours[i] } else { 1 };
+                }
+            },
+            None => clipped += 1,
+        }
+    }
+
+    clipped
+}
+
+/// [Tile Pattern]
+/// Clears a state array `cols` cells wide and `rows` cells tall, and fills
+/// it with copies of a pattern in a regular grid starting from the upper
+/// left corner, with `spacing` dead cells between neighbouring copies.
+/// Copies overhanging the right and bottom edges are clipped. Any colours
+/// are cleared along with the cells, and every copy takes the first.
+fn tile_pattern(state: &mut Cells, mut colours: Option<&mut [u8]>, cols: usize, rows: usize, pattern: &Pattern, spacing: usize) {
+    state.fill(false);
+    if let Some(colours) = colours.as_mut() {
+        colours.fill(0);
+    }
+    for top in (0..rows).step_by((pattern.height + spacing).max(1)) {
+        for left in (0..cols).step_by((pattern.width + spacing).max(1)) {
+            stamp(state, colours.as_deref_mut(), cols, rows, pattern, left as isize, top as isize, Blend::Or);
+        }
+    }
+}
+
+/// [Read Stdin]
+/// Reads a plaintext board of `.` (dead) and `#` (alive) rows from
+/// standard input, until it is closed. No input at all gives an empty
+/// pattern, as does a terminal, which is never read from rather than
+/// waiting on someone to type a board in.
+fn read_stdin() -> Result<Pattern, String> {
+    use std::io::{IsTerminal, Read};
+
+    let mut text = String::new();
+    if std::io::stdin().is_terminal() {
+        println!("Standard input is a terminal rather than a pipe, so the board starts out empty.");
+    } else {
+        std::io::stdin().read_to_string(&mut text).map_err(|e| format!("Could not read standard input: {}", e))?;
+    }
+    pattern::parse_cells(&text)
+}
+
+/// [Image Pattern]
+/// Reads an image as a pattern, in which every dark, opaque pixel is a
+/// living cell. Images too large for a board `cols` cells wide and `rows`
+/// cells tall are scaled down to fit, keeping their aspect ratio.
+fn image_pattern(path: &str, cols: usize, rows: usize) -> Result<Pattern, String> {
+    // Pixels darker than this, and more opaque, are alive.
+    const THRESHOLD: u8 = 128;
+
+    let mut picture = image::open(path).map_err(|e| format!("Could not read {}: {}", path, e))?;
+    if picture.width() as usize > cols || picture.height() as usize > rows {
+        picture = picture.resize(cols as u32, rows as u32, image::imageops::FilterType::Triangle);
+    }
+
+    let picture = picture.to_luma_alpha8();
+    let cells = picture.enumerate_pixels()
+        .filter(|(_, _, pixel)| pixel[0] < THRESHOLD && pixel[1] >= THRESHOLD)
+        .map(|(x, y, _)| (x as usize, y as usize))
+        .collect();
+
+    Ok(Pattern { width: picture.width() as usize, height: picture.height() as usize, cells })
+}
+
+/// [State Hash]
+/// Hashes the packed words of a board's cells, so that generations can be
+/// compared cheaply. This costs a tiny fraction of a generation.
+fn state_hash(state: &Cells) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    state.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// [Cycle History]
+/// Hashes of the most recent generations of a board, for telling when it
+/// has settled into a still life (period 1) or an oscillator of at most
+/// `MAX_PERIOD` generations.
+///
+/// Fields:
+/// [hashes] Hashes of the most recent generations, with the latest last;
+/// [period] Period of the cycle the board has settled into, if it has.
+#[derive(Default)]
+struct CycleHistory {
+    hashes: VecDeque<u64>,
+    period: Option<usize>
+}
+
+impl CycleHistory {
+
+    /// [Observe]
+    /// Records the latest generation, and returns the period of the cycle
+    /// it shows the board has settled into, only should it be a new one.
+    fn observe(&mut self, state: &Cells) -> Option<usize> {
+        let hash = state_hash(state);
+        let period = self.hashes.iter().rev().position(|&previous| previous == hash).map(|i| i + 1);
+        let settled = period.filter(|_| period != self.period);
+        self.period = period;
+
+        self.hashes.push_back(hash);
+        if self.hashes.len() > MAX_PERIOD {
+            self.hashes.pop_front();
+        }
+        settled
+    }
+
+    /// [Clear]
+    /// Forgets every generation recorded, and any cycle found among them.
+    fn clear(&mut self) {
+        self.hashes.clear();
+        self.period = None;
+    }
+}
+
+/// [Stamp Origin]
+/// Column and row at which the upper left corner of a pattern lies once
+/// centred on the given cell.
+fn stamp_origin((cell_x, cell_y): (usize, usize), pattern: &Pattern) -> (isize, isize) {
+    (cell_x as isize - (pattern.width / 2) as isize, cell_y as isize - (pattern.height / 2) as isize)
+}
+
+/// [Randomize]
+/// Brings each cell of the state array to life at random, with the given
+/// probability, and kills it otherwise.
+fn randomize(state: &mut Cells, rng: &mut StdRng, density: f64) {
+    for i in 0..state.len() {
+        state.set(i, rng.gen_bool(density));
+    }
+}
+
+/// [Immigrant Colours]
+/// Colours every cell of the state array for the given coloured variant,
+/// giving each living cell any of its colours at random, and every dead
+/// cell none.
+fn immigrant_colours(state: &Cells, rng: &mut StdRng, colouring: Colouring) -> Vec<u8> {
+    let colours = colouring.colours() as u8;
+    state.iter().map(|alive| if alive { rng.gen_range(1..=colours) } else { 0 }).collect()
+}
+
+/// [Symmetric Randomize]
+/// Randomizes the upper left quadrant of a state array `cols` cells wide
+/// and `rows` cells tall like `randomize`, then mirrors it across both
+/// axes into the other three quadrants. In odd dimensions the middle row
+/// or column is its own mirror image, so it is only drawn once.
+fn symmetric_randomize(state: &mut Cells, cols: usize, rows: usize, rng: &mut StdRng, density: f64) {
+    for y in 0..rows.div_ceil(2) {
+        for x in 0..cols.div_ceil(2) {
+            let alive = rng.gen_bool(density);
+            for &(x, y) in &[(x, y), (cols - 1 - x, y), (x, rows - 1 - y), (cols - 1 - x, rows - 1 - y)] {
+                state.set(x + y * cols, alive);
+            }
+        }
+    }
+}
+
+/// [Live Pattern]
+/// Collects the living cells of a state array `cols` cells wide into a
+/// pattern, trimmed to their bounding box. Returns nothing if the board
+/// is empty.
+fn live_pattern(state: &Cells, cols: usize) -> Option<Pattern> {
+    let live: Vec<(usize, usize)> = (0..state.len())
+        .filter(|&i| state.get(i))
+        .map(|i| (i % cols, i / cols))
+        .collect();
+
+    let left = live.iter().map(|&(x, _)| x).min()?;
+    let top = live.iter().map(|&(_, y)| y).min()?;
+    let right = live.iter().map(|&(x, _)| x).max()?;
+    let bottom = live.iter().map(|&(_, y)| y).max()?;
+
+    Some(Pattern {
+        width: right - left + 1,
+        height: bottom - top + 1,
+        cells: live.into_iter().map(|(x, y)| (x - left, y - top)).collect(),
+    })
+}
+
+/// [Slot Of]
+/// The quick-save slot selected by a number key, counting from zero.
+fn slot_of(key: piston::input::Key) -> Option<usize> {
+    use piston::input::Key;
+
+    [Key::D1, Key::D2, Key::D3, Key::D4, Key::D5, Key::D6, Key::D7, Key::D8, Key::D9]
+        .iter()
+        .position(|&digit| digit == key)
+}
+
+/// [Snapshot]
+/// A copy of the game board, taken before it is changed so that the
+/// change may be undone.
+///
+/// Fields:
+/// [cols] Width of the game board, in cells;
+/// [rows] Height of the game board, in cells;
+/// [state] State of the game board;
+/// [colours] Colour of every cell, while a coloured variant was being played;
+/// [generation] Number of generations computed since the board was last reset.
+#[derive(Clone)]
+struct Snapshot {
+    cols: usize,
+    rows: usize,
+    state: Cells,
+    colours: Option<Vec<u8>>,
+    generation: u64
+}
+
+/// [Timings]
+/// Running totals of how long generations took to compute, since they
+/// were last reported.
+///
+/// Fields:
+/// [count] Number of generations timed;
+/// [total] Combined time taken by every generation timed;
+/// [min] Shortest time taken by a generation;
+/// [max] Longest time taken by a generation;
+/// [since] Time at which the totals were last reported.
+struct Timings {
+    count: u32,
+    total: Duration,
+    min: Duration,
+    max: Duration,
+    since: Instant
+}
+
+impl Timings {
+
+    /// [New]
+    /// Creates totals covering no generations at all.
+    fn new() -> Timings {
+        Timings { count: 0, total: Duration::ZERO, min: Duration::MAX, max: Duration::ZERO, since: Instant::now() }
+    }
+
+    /// [Add]
+    /// Counts another generation, which took the given time.
+    fn add(&mut self, elapsed: Duration) {
+        self.count += 1;
+        self.total += elapsed;
+        self.min = self.min.min(elapsed);
+        self.max = self.max.max(elapsed);
+    }
+
+    /// [Report]
+    /// Once at least a second has passed since the totals were last
+    /// reported, prints the average, shortest and longest times taken
+    /// since then, and starts over.
+    fn report(&mut self) {
+        if self.since.elapsed() < Duration::from_secs(1) || self.count == 0 {
+            return;
+        }
+
+        let millis = |duration: Duration| duration.as_secs_f64() * 1000.0;
+        println!("Updated {} generations in {:.3}ms on average (min {:.3}ms, max {:.3}ms)",
+                 self.count, millis(self.total) / self.count as f64, millis(self.min), millis(self.max));
+        *self = Timings::new();
+    }
+}
+
+/// [Clipboard]
+/// A rectangular region of cells copied from the board.
+///
+/// Fields:
+/// [width] Number of columns in the region;
+/// [height] Number of rows in the region;
+/// [cells] Whether each cell of the region is alive, row by row;
+/// [colours] Colour of each cell of the region, row by row, should a coloured variant have been played.
+struct Clipboard {
+    width: usize,
+    height: usize,
+    cells: Vec<bool>,
+    colours: Option<Vec<u8>>
+}
+
+/// [App]
+/// The App struct defines the Piston application and associated
+/// data. All fields within this structure are statically accessible
+/// from within the application's associated methods.
+///
+/// Fields:
+/// [gl] OpenGL graphics backend;
+/// [board] The game board, which is simulated independently of the window;
+/// [cell_size] Width and height of each cell on screen before any zooming, in pixels;
+/// [cursor_pos] Actively tracked location of the user's mouse cursor;
+/// [paused] Game state;
+/// [pixels] RGBA pixel buffer holding one pixel per cell;
+/// [texture] Texture the pixel buffer is uploaded to, if one could be created;
+/// [filter] How the board texture is sampled when drawn larger or smaller than one pixel per cell;
+/// [grid] Whether grid lines are drawn between cells;
+/// [age] Number of generations each cell has stayed alive for;
+/// [age_colours] Whether living cells are coloured by their age;
+/// [gen_interval] Minimum time between generations, where zero means unlimited;
+/// [speed_held] Whether a key speeding the game up, or slowing it down, is held, and since when;
+/// [last_gen] Time at which the latest generation was computed;
+/// [offset] Position of the board's upper left corner within the window, in pixels;
+/// [scale] Width and height of each cell on screen, in pixels;
+/// [panning] Whether the board is being dragged with the middle mouse button;
+/// [brush_radius] Radius of the square brush used to draw cells, where zero toggles one cell;
+/// [drawing] Whether a mouse button which draws or erases cells is held down;
+/// [drawing_alive] Whether dragging brings cells to life, rather than erasing them;
+/// [press_cursor] Position of the cursor when the drawing button was pressed;
+/// [dragging] Whether the cursor has moved far enough since the press to paint, rather than click;
+/// [rng] Random number generator behind every random board, seeded for reproducibility;
+/// [density] Probability of each cell being alive on a random board;
+/// [glyphs] Cache of the font's glyphs, for drawing text;
+/// [generation] Number of generations computed since the board was last reset;
+/// [undo] Snapshots of the board before each change, with the latest last;
+/// [redo] Snapshots of the board before each undo, with the latest last;
+/// [clear_pressed] Time at which clearing a busy board was last asked to be confirmed, if it is still awaiting confirmation;
+/// [ctrl] Whether either control key is held down;
+/// [shift] Whether either shift key is held down;
+/// [slots] Boards quick-saved to each of the nine slots, if any;
+/// [log] CSV file each generation's population and update time are appended to, if any;
+/// [history] Hashes of the generations computed since the board was last changed otherwise, for finding cycles;
+/// [pause_on_cycle] Whether the game pauses once the board settles into a cycle;
+/// [max_generation] Generation at which the program exits, if any;
+/// [pause_at] Generation at which the game pauses, until it first gets there;
+/// [gens_per_frame] Greatest number of generations computed per update;
+/// [window_size] Current size of the window, in pixels;
+/// [selection] Column and row of the cells at opposite corners of the selected rectangle, if any;
+/// [selecting] Whether the selection is being dragged out with the mouse;
+/// [clipboard] Region of cells most recently copied, if any;
+/// [fade] Number of frames each recently died cell is still drawn for, fading away;
+/// [fading] Whether dying cells fade away, rather than vanishing at once;
+/// [theme] Index of the colour scheme the board is drawn in, within `THEMES`;
+/// [following] Whether the view keeps the centroid of every living cell centred in the window;
+/// [cell_shape] Shape each living cell is drawn as;
+/// [timeline] Recent generations of the board, oldest first, for scrubbing through;
+/// [timeline_pos] Index of the generation being shown within the timeline;
+/// [timeline_len] Greatest number of generations the timeline holds;
+/// [minimap] Whether a downscaled view of the whole board is shown in the lower right corner;
+/// [minimap_texture] Texture holding the minimap, as of its latest refresh;
+/// [minimap_since] Time at which the minimap was last refreshed;
+/// [graph] Whether a graph of the population over recent generations is shown;
+/// [populations] Populations of the last `GRAPH_LEN` generations computed, oldest first;
+/// [patterns] Name and contents of every pattern loaded for placing with the mouse;
+/// [placing] Index of the pattern stamped by left clicks, if any;
+/// [blend] How stamped patterns combine with the cells beneath them;
+/// [clusters] Number of separate clusters of living cells and the size of the largest, as last counted on this generation;
+/// [rule_input] Rule being typed in, while keys edit it rather than the board;
+/// [rule_error] Why the rule last entered could not be applied, while it is still being typed in;
+/// [edges_shown] Whether edges of a torus with living cells along them are marked, as cells wrap around them;
+/// [bounds_shown] Whether the bounding box of every living cell is outlined;
+/// [bounds] Leftmost column, topmost row, rightmost column and bottom row holding a living cell, as of the latest change;
+/// [tile_spacing] Number of dead cells left between tiles when tiling the board with a pattern;
+/// [verbose] Whether the times taken by generations are reported every second;
+/// [timings] Times taken by generations since they were last reported;
+/// [thread_stats_since] When the work done by each thread was last reported, if it is being reported at all;
+/// [threads] Number of threads generations are computed on;
+/// [config_path] Path of the file the settings are saved to;
+/// [frozen] Whether drawing the board is paused, while generations carry on;
+/// [generation_rate] Generations computed per second, as last measured;
+/// [dirty] Whether the colour of any cell may have changed since the last frame;
+/// [frames] Number of frames rendered since the rates were last measured;
+/// [generations] Number of generations computed since the rates were last measured;
+/// [rates_since] Time at which the rates were last measured.
+pub struct App {
+    gl: GlGraphics,
+    board: Board,
+    cell_size: [usize; 2],
+    cursor_pos: [f64; 2],
+    paused: bool,
+    pixels: Vec<u8>,
+    texture: Option<Texture>,
+    filter: Filter,
+    grid: bool,
+    age: Vec<u16>,
+    age_colours: bool,
+    gen_interval: Duration,
+    speed_held: Option<(bool, Instant)>,
+    last_gen: Instant,
+    offset: [f64; 2],
+    scale: [f64; 2],
+    panning: bool,
+    brush_radius: usize,
+    drawing: bool,
+    drawing_alive: bool,
+    press_cursor: [f64; 2],
+    dragging: bool,
+    rng: StdRng,
+    density: f64,
+    glyphs: GlyphCache<'static>,
+    generation: u64,
+    undo: Vec<Snapshot>,
+    redo: Vec<Snapshot>,
+    clear_pressed: Option<Instant>,
+    ctrl: bool,
+    shift: bool,
+    slots: [Option<Snapshot>; 9],
+    log: Option<BufWriter<File>>,
+    history: CycleHistory,
+    pause_on_cycle: bool,
+    max_generation: Option<u64>,
+    pause_at: Option<u64>,
+    gens_per_frame: u32,
+    window_size: [f64; 2],
+    selection: Option<[(usize, usize); 2]>,
+    selecting: bool,
+    clipboard: Option<Clipboard>,
+    fade: Vec<u8>,
+    fading: bool,
+    theme: usize,
+    following: bool,
+    cell_shape: CellShape,
+    timeline: VecDeque<Snapshot>,
+    timeline_pos: usize,
+    timeline_len: usize,
+    minimap: bool,
+    minimap_texture: Option<Texture>,
+    minimap_since: Instant,
+    graph: bool,
+    populations: VecDeque<usize>,
+    patterns: Vec<(String, Pattern)>,
+    placing: Option<usize>,
+    blend: Blend,
+    clusters: Option<(usize, usize)>,
+    rule_input: Option<String>,
+    rule_error: Option<String>,
+    edges_shown: bool,
+    bounds_shown: bool,
+    bounds: Option<(usize, usize, usize, usize)>,
+    tile_spacing: usize,
+    verbose: bool,
+    timings: Timings,
+    thread_stats_since: Option<Instant>,
+    threads: usize,
+    config_path: String,
+    frozen: bool,
+    generation_rate: f64,
+    dirty: bool,
+    frames: u32,
+    generations: u32,
+    rates_since: Instant
+}
+
+/// [App]
+/// Application related methods.
+impl App {
+
+    /// [Render]
+    /// The render method is required by Piston in order to service
+    /// the application control-flow, using callbacks. The render
+    /// method is specifically meant to be where all calls to OpenGL
+    /// happen, and is meant to be called every frame.
+    ///
+    /// This program implements the render method by writing the colour
+    /// of each cell of the game's state into a pixel buffer, which is
+    /// uploaded as a single texture and stretched across the window.
+    /// Should no texture be available, the living cells are instead
+    /// drawn as individual squares upon a blank background.
+    ///
+    /// Being a Piston callback, its only parameters are itself,
+    /// and the Piston render arguments.
+    fn render(&mut self, args: &RenderArgs) {
+
+        // Local constants:
+        const GRAY: [f32; 4] = [0.78, 0.76, 0.74, 1.0];
+        const BACKDROP: [f32; 4] = [0.2, 0.2, 0.2, 1.0];
+        const PANEL: [f32; 4] = [0.9, 0.9, 0.85, 0.8];
+        const INK: [f32; 4] = [0.2, 0.2, 0.2, 1.0];
+        const SELECTION: [f32; 4] = [0.2, 0.5, 0.9, 1.0];
+        const BOUNDS: [f32; 4] = [0.85, 0.2, 0.7, 1.0];
+        const WRAPPING: [f32; 4] = [0.95, 0.55, 0.1, 1.0];
+        const HOVER: [f32; 4] = [0.1, 0.8, 0.9, 1.0];
+        const GRAPH: [f32; 4] = [0.85, 0.2, 0.2, 1.0];
+
+        // While rendering is frozen, only a notice of it is drawn, which
+        // leaves the cost of each generation to be measured on its own.
+        if self.frozen {
+            let notice = format!("Rendering paused  Generation: {}  {:.0} Generations/s", self.generation, self.generation_rate);
+            let glyphs = &mut self.glyphs;
+            self.gl.draw(args.viewport(), |c, gl| {
+                clear(BACKDROP, gl);
+                rectangle(PANEL, [0.0, 0.0, 10.0 + 8.5 * notice.len() as f64, 24.0], c.transform, gl);
+                text(INK, 14, &notice, glyphs, c.transform.trans(5.0, 17.0), gl)
+                    .expect("Could not draw the rendering notice!");
+            });
+            return;
+        }
+
+        let colour_of = self.colour_of();
+
+        // The bounding box only needs finding again once cells have changed.
+        if self.bounds_shown && self.dirty {
+            self.bounds = self.board.bounding_box();
+        }
+
+        // The texture holds one square pixel per cell, so other shapes are
+        // drawn individually.
+        let texture = if self.cell_shape == CellShape::Square { self.texture.as_mut() } else { None };
+        if let Some(texture) = texture {
+            // Rewrite only the pixels whose colour no longer matches their cell,
+            // and only upload them when any might have changed.
+            if self.dirty {
+                let cells = self.board.state.iter().zip(self.age.iter()).zip(self.fade.iter());
+                let colours = self.board.colours.as_deref();
+                for (i, (((cell, age), fade), pixel)) in cells.zip(self.pixels.chunks_exact_mut(4)).enumerate() {
+                    let immigrant = colours.map_or(0, |colours| colours[i].max(1));
+                    let mut colour = colour_of(cell, *age, *fade, immigrant);
+                    if let Some(count) = self.board.counts.as_ref().map(|counts| counts[i]).filter(|&count| count > 0) {
+                        colour = mix(colour, NEIGHBOUR_TINTS[count as usize - 1], 0.6);
+                    }
+                    let colour = to_rgba8(colour);
+                    if *pixel != colour {
+                        pixel.copy_from_slice(&colour);
+                    }
+                }
+
+                UpdateTexture::update(texture, &mut (), Format::Rgba8, &self.pixels, [0, 0], [self.board.cols as u32, self.board.rows as u32])
+                    .expect("Could not update the board texture!");
+            }
+
+            // Draw the pixel buffer as one quad covering the board.
+            let (offset, scale) = (self.offset, self.scale);
+            self.gl.draw(args.viewport(), |c, gl| {
+                clear(BACKDROP, gl);
+                image(texture, board_transform(c.transform, offset, scale), gl);
+            });
+        } else {
+            let background = THEMES[self.theme].background;
+            self.render_shapes(args, BACKDROP, background, colour_of);
+        }
+
+        // The grid is drawn on top of the cells, with one line along every
+        // cell border. It would be far too dense to be useful at small scales.
+        if self.grid && self.scale[0].min(self.scale[1]) >= 8.0 {
+            let (offset, scale) = (self.offset, self.scale);
+            let (left, top, right, bottom) = self.visible_cells();
+            self.gl.draw(args.viewport(), |c, gl| {
+                let transform = board_transform(c.transform, offset, scale);
+                let radius = 0.5 / scale[0].min(scale[1]);
+                for x in left..=right {
+                    let x = x as f64;
+                    line(GRAY, radius, [x, top as f64, x, bottom as f64], transform, gl);
+                }
+                for y in top..=bottom {
+                    let y = y as f64;
+                    line(GRAY, radius, [left as f64, y, right as f64, y], transform, gl);
+                }
+            });
+        }
+
+        // The cell under the cursor is outlined, so that it is clear which
+        // cell a click would change, unless the cursor is off the board.
+        if let Some((x, y)) = self.cell_at([0.0, 0.0]) {
+            let (offset, scale) = (self.offset, self.scale);
+            self.gl.draw(args.viewport(), |c, gl| {
+                Rectangle::new_border(HOVER, 0.5 / scale[0].min(scale[1]))
+                    .draw(rectangle::square(x as f64, y as f64, 1.0), &c.draw_state, board_transform(c.transform, offset, scale), gl);
+            });
+        }
+
+        // Edges of a torus which living cells lie along are marked, as those
+        // cells see, and may soon cross onto, the opposite edge.
+        if self.edges_shown && self.board.boundary == Boundary::Torus {
+            let [left, top, right, bottom] = self.board.live_edges();
+            let (cols, rows) = (self.board.cols as f64, self.board.rows as f64);
+            let (offset, scale) = (self.offset, self.scale);
+            self.gl.draw(args.viewport(), |c, gl| {
+                let transform = board_transform(c.transform, offset, scale);
+                let radius = 1.0 / scale[0].min(scale[1]);
+                let edges = [(left, [0.0, 0.0, 0.0, rows]), (top, [0.0, 0.0, cols, 0.0]),
+                             (right, [cols, 0.0, cols, rows]), (bottom, [0.0, rows, cols, rows])];
+                for &(_, edge) in edges.iter().filter(|&&(alive, _)| alive) {
+                    line(WRAPPING, radius, edge, transform, gl);
+                }
+            });
+        }
+
+        // The selected rectangle is outlined one pixel wide.
+        if let Some((left, top, width, height)) = self.selection_bounds() {
+            let (offset, scale) = (self.offset, self.scale);
+            self.gl.draw(args.viewport(), |c, gl| {
+                let outline = [left as f64, top as f64, width as f64, height as f64];
+                Rectangle::new_border(SELECTION, 1.0 / scale[0].min(scale[1]))
+                    .draw(outline, &c.draw_state, board_transform(c.transform, offset, scale), gl);
+            });
+        }
+
+        // So is the bounding box of the living cells, unless there are none.
+        if let Some((left, top, right, bottom)) = self.bounds.filter(|_| self.bounds_shown) {
+            let (offset, scale) = (self.offset, self.scale);
+            self.gl.draw(args.viewport(), |c, gl| {
+                let outline = [left as f64, top as f64, (right - left + 1) as f64, (bottom - top + 1) as f64];
+                Rectangle::new_border(BOUNDS, 1.0 / scale[0].min(scale[1]))
+                    .draw(outline, &c.draw_state, board_transform(c.transform, offset, scale), gl);
+            });
+        }
+
+        // The pattern being placed is previewed faintly under the cursor, as
+        // blended with the board: cells it would bring to life in the
+        // foreground colour, and cells it would kill in the background colour.
+        // Only the pattern's living cells and, when replacing, the board's
+        // within its bounds are visited, so a pattern declared far larger
+        // than it is costs nothing extra.
+        if let (Some(index), Some(cell)) = (self.placing, self.cell_at([0.0, 0.0])) {
+            let pattern = &self.patterns[index].1;
+            let (left, top) = stamp_origin(cell, pattern);
+            let (cols, rows) = (self.board.cols as isize, self.board.rows as isize);
+            let on_board = |x: isize, y: isize| x >= 0 && x < cols && y >= 0 && y < rows;
+            let existing = |x: isize, y: isize| on_board(x, y) && self.board.state.get((x + y * cols) as usize);
+            let stamped: HashSet<(isize, isize)> = pattern.cells.iter()
+                .map(|&(x, y)| (left + x as isize, top + y as isize))
+                .filter(|&(x, y)| on_board(x, y))
+                .collect();
+
+            let mut preview: Vec<(isize, isize, bool)> = stamped.iter()
+                .map(|&(x, y)| (x, y, self.blend.apply(existing(x, y), true)))
+                .filter(|&(x, y, alive)| alive || existing(x, y))
+                .collect();
+            if self.blend == Blend::Replace {
+                for y in top.max(0)..(top + pattern.height as isize).min(rows) {
+                    for x in left.max(0)..(left + pattern.width as isize).min(cols) {
+                        if existing(x, y) && !stamped.contains(&(x, y)) {
+                            preview.push((x, y, false));
+                        }
+                    }
+                }
+            }
+
+            let Theme { background, foreground, .. } = THEMES[self.theme];
+            let faint = |[red, green, blue, _]: [f32; 4]| [red, green, blue, 0.5];
+            let (offset, scale) = (self.offset, self.scale);
+            self.gl.draw(args.viewport(), |c, gl| {
+                let transform = board_transform(c.transform, offset, scale);
+                for &(x, y, alive) in &preview {
+                    let colour = faint(if alive { foreground } else { background });
+                    rectangle(colour, rectangle::square(x as f64, y as f64, 1.0), transform, gl);
+                }
+            });
+        }
+
+        // The minimap sits in the lower right corner, marking the part of the
+        // board within the window.
+        if self.minimap {
+            self.refresh_minimap();
+            let (block, size, rect) = self.minimap_layout();
+            let zoom = rect[2] / size[0] as f64;
+            let per_cell = zoom / block as f64;
+
+            let view = [-self.offset[0] / self.scale[0], -self.offset[1] / self.scale[1]];
+            let left = (rect[0] + view[0] * per_cell).max(rect[0]);
+            let top = (rect[1] + view[1] * per_cell).max(rect[1]);
+            let right = (rect[0] + (view[0] + self.window_size[0] / self.scale[0]) * per_cell).min(rect[0] + rect[2]);
+            let bottom = (rect[1] + (view[1] + self.window_size[1] / self.scale[1]) * per_cell).min(rect[1] + rect[3]);
+
+            if let Some(texture) = self.minimap_texture.as_ref() {
+                self.gl.draw(args.viewport(), |c, gl| {
+                    image(texture, c.transform.trans(rect[0], rect[1]).scale(zoom, zoom), gl);
+                    Rectangle::new_border(GRAY, 1.0).draw(rect, &c.draw_state, c.transform, gl);
+                    if right > left && bottom > top {
+                        Rectangle::new_border(SELECTION, 1.0)
+                            .draw([left, top, right - left, bottom - top], &c.draw_state, c.transform, gl);
+                    }
+                });
+            }
+        }
+
+        // The population graph sits in the lower left corner, with the newest
+        // generation on the right, and scaled to the largest population shown.
+        if self.graph && self.populations.len() > 1 {
+            const GRAPH_SIZE: [f64; 2] = [300.0, 100.0];
+            const MARGIN: f64 = 10.0;
+
+            let rect = [MARGIN, self.window_size[1] - GRAPH_SIZE[1] - MARGIN, GRAPH_SIZE[0], GRAPH_SIZE[1]];
+            let most = self.populations.iter().copied().max().unwrap_or(0).max(1);
+            let spacing = rect[2] / (GRAPH_LEN - 1) as f64;
+            let newest = self.populations.len() - 1;
+            let points: Vec<[f64; 2]> = self.populations.iter().enumerate()
+                .map(|(i, &population)| [rect[0] + rect[2] - (newest - i) as f64 * spacing,
+                                         rect[1] + rect[3] * (1.0 - population as f64 / most as f64)])
+                .collect();
+            let label = format!("Population (max {})", most);
+
+            let glyphs = &mut self.glyphs;
+            self.gl.draw(args.viewport(), |c, gl| {
+                rectangle(PANEL, rect, c.transform, gl);
+                for pair in points.windows(2) {
+                    line_from_to(GRAPH, 1.0, pair[0], pair[1], c.transform, gl);
+                }
+                Rectangle::new_border(GRAY, 1.0).draw(rect, &c.draw_state, c.transform, gl);
+                text(INK, 12, &label, glyphs, c.transform.trans(rect[0] + 4.0, rect[1] + 14.0), gl)
+                    .expect("Could not draw the population graph!");
+            });
+        }
+
+        // Dying cells fade a little further with every frame drawn, and the
+        // board stays dirty until the last of them has been drawn gone.
+        if self.dirty {
+            let mut fading = false;
+            for fade in self.fade.iter_mut().filter(|fade| **fade > 0) {
+                *fade -= 1;
+                fading = true;
+            }
+            self.dirty = fading;
+        }
+
+        // The statistics are drawn last, in the upper left corner of the window,
+        // along with a label in the upper right corner while the game is paused.
+        let mut stats = match self.timeline.back() {
+            Some(newest) if self.timeline_pos + 1 < self.timeline.len() => {
+                format!("Generation: {} of {}  Population: {}", self.generation, newest.generation, self.board.population)
+            },
+            _ => format!("Generation: {}  Population: {}", self.generation, self.board.population),
+        };
+        match (self.board.larger_than_life, self.board.rule.name()) {
+            (Some(rule), _) => stats.push_str(&format!("  Rule: {}", rule)),
+            (None, Some(name)) => stats.push_str(&format!("  Rule: {} ({})", name, self.board.rule)),
+            (None, None) => stats.push_str(&format!("  Rule: {}", self.board.rule)),
+        }
+        if let Some((count, largest)) = self.clusters {
+            stats.push_str(&format!("  Clusters: {} (largest {})", count, largest));
+        }
+
+        // A rule being typed in is shown beneath them, along with why it was rejected.
+        let prompt = self.rule_input.as_ref().map(|input| format!("New rule: {}_  (Enter to apply, Esc to cancel)", input));
+        let lines: Vec<String> = prompt.into_iter().chain(self.rule_error.clone()).collect();
+
+        let (paused, window_width) = (self.paused, args.window_size[0]);
+        let glyphs = &mut self.glyphs;
+        self.gl.draw(args.viewport(), |c, gl| {
+            rectangle(PANEL, [0.0, 0.0, 10.0 + 8.5 * stats.len() as f64, 24.0], c.transform, gl);
+            text(INK, 14, &stats, glyphs, c.transform.trans(5.0, 17.0), gl)
+                .expect("Could not draw the statistics!");
+
+            for (row, line) in lines.iter().enumerate() {
+                let top = 24.0 * (row + 1) as f64;
+                rectangle(PANEL, [0.0, top, 10.0 + 8.5 * line.len() as f64, 24.0], c.transform, gl);
+                text(INK, 14, line, glyphs, c.transform.trans(5.0, top + 17.0), gl)
+                    .expect("Could not draw the rule being typed in!");
+            }
+
+            if paused {
+                const LABEL: &str = "PAUSED";
+                let width = 10.0 + 8.5 * LABEL.len() as f64;
+                rectangle(PANEL, [window_width - width, 0.0, width, 24.0], c.transform, gl);
+                text(INK, 14, LABEL, glyphs, c.transform.trans(window_width - width + 5.0, 17.0), gl)
+                    .expect("Could not draw the pause label!");
+            }
+        });
+    }
+
+    /// [Minimap Layout]
+    ///
+    /// Number of cells along each side of the square block shown by each
+    /// pixel of the minimap, along with the minimap's width and height in
+    /// such pixels, and the rectangle it covers in the lower right corner
+    /// of the window.
+    fn minimap_layout(&self) -> (usize, [usize; 2], [f64; 4]) {
+        const MINIMAP_SIDE: usize = 200;
+        const MARGIN: f64 = 10.0;
+
+        let (cols, rows) = (self.board.cols, self.board.rows);
+        let block = cols.max(rows).div_ceil(MINIMAP_SIDE).max(1);
+        let size = [cols.div_ceil(block), rows.div_ceil(block)];
+        let zoom = MINIMAP_SIDE as f64 / size[0].max(size[1]) as f64;
+        let (width, height) = (size[0] as f64 * zoom, size[1] as f64 * zoom);
+
+        (block, size, [self.window_size[0] - width - MARGIN, self.window_size[1] - height - MARGIN, width, height])
+    }
+
+    /// [Refresh Minimap]
+    ///
+    /// Redraws the minimap, with each of its pixels showing whether any
+    /// cell within its block is alive. This happens at most a few times a
+    /// second, so that the minimap stays cheap on large boards.
+    fn refresh_minimap(&mut self) {
+        const INTERVAL: Duration = Duration::from_millis(250);
+
+        if self.minimap_texture.is_some() && self.minimap_since.elapsed() < INTERVAL {
+            return;
+        }
+        self.minimap_since = Instant::now();
+
+        let (block, [width, height], _) = self.minimap_layout();
+        let cols = self.board.cols;
+        let mut alive = vec![false; width * height];
+        for i in self.board.state.alive_indices() {
+            alive[(i % cols) / block + (i / cols) / block * width] = true;
+        }
+
+        let Theme { background, foreground, .. } = THEMES[self.theme];
+        let pixels: Vec<u8> = alive.iter()
+            .flat_map(|&alive| to_rgba8(if alive { foreground } else { background }))
+            .collect();
+        self.minimap_texture = board_texture(&pixels, width, height, Filter::Nearest).ok();
+    }
+
+    /// [Recentre On Minimap]
+    ///
+    /// Centres the view on the part of the board shown beneath a point
+    /// within the window, should the minimap be shown there. Returns
+    /// whether it was.
+    fn recentre_on_minimap(&mut self, point: [f64; 2]) -> bool {
+        if !self.minimap {
+            return false;
+        }
+
+        let (block, size, rect) = self.minimap_layout();
+        if point[0] < rect[0] || point[1] < rect[1] || point[0] >= rect[0] + rect[2] || point[1] >= rect[1] + rect[3] {
+            return false;
+        }
+
+        let per_cell = rect[2] / (size[0] * block) as f64;
+        for axis in 0..2 {
+            let cell = (point[axis] - rect[axis]) / per_cell;
+            self.offset[axis] = self.window_size[axis] / 2.0 - cell * self.scale[axis];
+        }
+        true
+    }
+
+    /// [Rates]
+    ///
+    /// Counts another rendered frame, and once at least a second has
+    /// passed since the rates were last measured, measures the frames and
+    /// generations per second achieved since then.
+    fn rates(&mut self) -> Option<(f64, f64)> {
+        self.frames += 1;
+
+        let elapsed = self.rates_since.elapsed().as_secs_f64();
+        if elapsed < 1.0 {
+            return None;
+        }
+
+        let rates = (self.frames as f64 / elapsed, self.generations as f64 / elapsed);
+        self.generation_rate = rates.1;
+        self.frames = 0;
+        self.generations = 0;
+        self.rates_since = Instant::now();
+        Some(rates)
+    }
+
+    /// [Colour Of]
+    /// Provides the colour each cell is drawn in, given whether it is
+    /// alive, its age, and for how many more frames it fades away after
+    /// dying, and its colour in a coloured variant, if any. Living cells are
+    /// drawn in their variant's colour, or else either in the theme's foreground
+    /// colour or coloured by how many generations they have survived,
+    /// while dying cells blend from the foreground into the background.
+    fn colour_of(&self) -> impl Fn(bool, u16, u8, u8) -> [f32; 4] {
+        let age_colours = self.age_colours;
+        let Theme { background, foreground, .. } = THEMES[self.theme];
+        move |alive: bool, age: u16, fade: u8, colour: u8| {
+            if !alive && fade > 0 {
+                mix(background, foreground, fade as f32 / (FADE_FRAMES + 1) as f32)
+            } else if !alive {
+                background
+            } else if colour > 0 {
+                IMMIGRANTS[colour as usize - 1]
+            } else if age_colours {
+                age_colour(age)
+            } else {
+                foreground
+            }
+        }
+    }
+
+    /// [Immigrant]
+    ///
+    /// Colour of the cell at index `i` in a coloured variant, or zero when
+    /// none is being played. Living cells yet to be given a colour, such as
+    /// those just drawn in, take the first.
+    fn immigrant(&self, i: usize) -> u8 {
+        self.board.colours.as_ref().map_or(0, |colours| colours[i].max(1))
+    }
+
+    /// [Visible Cells]
+    ///
+    /// Leftmost column, topmost row, and the column and row just beyond
+    /// the rightmost and bottom ones, of the cells at least partly within
+    /// the window, as it is panned and zoomed. The range is clamped to the
+    /// board, and is empty should the board lie entirely outside the window.
+    fn visible_cells(&self) -> (usize, usize, usize, usize) {
+        let cells = [self.board.cols, self.board.rows];
+        let bounds = [0, 1].map(|axis| {
+            let first = (-self.offset[axis] / self.scale[axis]).floor();
+            let last = ((self.window_size[axis] - self.offset[axis]) / self.scale[axis]).ceil();
+            let clamp = |bound: f64| bound.clamp(0.0, cells[axis] as f64) as usize;
+            (clamp(first), clamp(last))
+        });
+        (bounds[0].0, bounds[1].0, bounds[0].1, bounds[1].1)
+    }
+
+    /// [Render Shapes]
+    /// Fallback for the render method, used whenever the board texture
+    /// could not be created, or cells are not drawn as squares. Draws each
+    /// living cell in its shape, in the colour given by `colour_of` for
+    /// its liveness, age and colour in any coloured variant, along with
+    /// each dying cell which has yet to fade away, upon a background of
+    /// the given colour, with the area around the board filled by the
+    /// backdrop colour.
+    fn render_shapes<F>(&mut self, args: &RenderArgs, backdrop: [f32; 4], background: [f32; 4], colour_of: F)
+        where F: Fn(bool, u16, u8, u8) -> [f32; 4] {
+
+        // The following block of code will overwrite the board with white,
+        // and then draw every living cell on top of it.
+        //
+        // All living cells are first collected into a single vector of squares,
+        // so that they may be submitted to OpenGL within one draw call rather
+        // than one draw call per cell. Only the cells within the window are
+        // visited at all, which keeps zoomed in views of huge boards cheap.
+        let mut squares: Vec<([f32; 4], [f64; 4])> = Vec::new();
+        let (left, top, right, bottom) = self.visible_cells();
+        for y in top..bottom {
+            for x in left..right {
+                let i = x + y * self.board.cols;
+
+                // We draw the living cell as a square, which is a data structure
+                // with 3 floating point values representing position and size,
+                // measured in cells.
+                if self.board.state.get(i) {
+                    squares.push((colour_of(true, self.age[i], 0, self.immigrant(i)), rectangle::square(x as f64, y as f64, 1.0)));
+                } else if self.fade[i] > 0 {
+                    squares.push((colour_of(false, 0, self.fade[i], 0), rectangle::square(x as f64, y as f64, 1.0)));
+                }
+            }
+        }
+
+        let (offset, scale, shape) = (self.offset, self.scale, self.cell_shape);
+        let (cols, rows) = (self.board.cols, self.board.rows);
+        self.gl.draw(args.viewport(), |c, gl| {
+            // Create the necessary components to draw with:
+            let background_fill =
+                rectangle::rectangle_by_corners(0.0, 0.0, cols as f64, rows as f64);
+            let transform = board_transform(c.transform, offset, scale);
+
+            // Collect all components and write to the screen.
+            clear(backdrop, gl);
+            rectangle(background, background_fill, transform, gl);
+
+            // Every cell shares the same transformation.
+            for (colour, square) in squares {
+                match shape {
+                    CellShape::Square => rectangle(colour, square, transform, gl),
+                    CellShape::Circle => ellipse(colour, square, transform, gl),
+                    CellShape::Rounded => Rectangle::new_round(colour, 0.25).draw(square, &c.draw_state, transform, gl),
+                }
+            }
+        });
+    }
+    
+    /// [Update]
+    ///
+    /// The update method is required by Piston in order to service
+    /// the application logic (as opposed to rendering) using callbacks.
+    /// The update method contains user-defined logic which does not
+    /// necessarily have to do with drawing to OpenGL.
+    ///
+    /// Therefore, this method advances the game state for the current
+    /// Game of Life instance by one generation, whenever the game is
+    /// un-paused and enough time has passed since the previous one to
+    /// honour the target simulation speed.
+    ///
+    /// Being a Piston callback, its only parameters are itself,
+    /// and the Piston update arguments.
+    fn update(&mut self, args: &UpdateArgs) {
+        // Holding a speed key for a moment ramps the speed smoothly, by
+        // `RAMP` times a second.
+        const RAMP: f64 = 4.0;
+        const RAMP_DELAY: Duration = Duration::from_millis(300);
+        if let Some((faster, since)) = self.speed_held {
+            if since.elapsed() >= RAMP_DELAY {
+                self.change_speed(faster, RAMP.powf(args.dt));
+            }
+        }
+
+        // Only update frames if the game is un-paused. Up to `gens_per_frame`
+        // generations are computed per update, but never for longer than
+        // `FRAME_BUDGET`, so that the window stays responsive.
+        const FRAME_BUDGET: Duration = Duration::from_millis(50);
+        let started = Instant::now();
+        for _ in 0..self.gens_per_frame {
+            if self.paused || self.finished() || self.last_gen.elapsed() < self.gen_interval || started.elapsed() >= FRAME_BUDGET {
+                break;
+            }
+            self.last_gen = Instant::now();
+            self.step();
+        }
+    }
+
+    /// [Finished]
+    ///
+    /// Whether the board has reached the generation at which the program
+    /// should exit.
+    fn finished(&self) -> bool {
+        self.max_generation.is_some_and(|max| self.generation >= max)
+    }
+
+    /// [Cycle Neighbourhood]
+    ///
+    /// Switches between counting all eight surrounding cells as neighbours,
+    /// only the four orthogonally adjacent ones, and only the four
+    /// diagonally adjacent ones.
+    fn cycle_neighbourhood(&mut self) {
+        self.board.neighbourhood = self.board.neighbourhood.next();
+        println!("Neighbourhood: {:?}", self.board.neighbourhood);
+    }
+
+    /// [Press Speed]
+    ///
+    /// Speeds the game up, or slows it down, by a small step once the key
+    /// for it is first pressed, then leaves `update` to ramp the speed for
+    /// as long as the key is held. Repeated presses from the key being
+    /// held are ignored.
+    fn press_speed(&mut self, faster: bool) {
+        // Four steps double or halve the speed.
+        const STEP: f64 = 1.189_207_115;
+
+        if self.speed_held.map(|(held, _)| held) != Some(faster) {
+            self.change_speed(faster, STEP);
+            self.report_speed();
+            self.speed_held = Some((faster, Instant::now()));
+        }
+    }
+
+    /// [Next Preset]
+    ///
+    /// Switches to the preset rule following the active one in
+    /// `board::PRESETS`, or to the first if the active rule is not a preset.
+    /// A Larger than Life rule is left behind for the first preset.
+    fn next_preset(&mut self) {
+        let next = match self.board.larger_than_life.take() {
+            Some(_) => 0,
+            None => board::PRESETS.iter().position(|&(_, rule)| rule == self.board.rule).map_or(0, |i| (i + 1) % board::PRESETS.len()),
+        };
+        let (name, rule) = board::PRESETS[next];
+        self.board.rule = rule;
+        println!("Rule: {} ({})", name, rule);
+    }
+
+    /// [Type Rule]
+    ///
+    /// Edits the rule being typed in, if any, with a keyboard or text
+    /// event, returning whether the event was used up. Typed characters
+    /// are appended and Backspace removes the last, while Enter applies
+    /// the rule, or shows why it could not be parsed and keeps it open for
+    /// correcting, and Escape leaves the rule as it was. Any other key
+    /// press is ignored, rather than acting on the board. Applying a rule
+    /// leaves any Larger than Life rule behind.
+    fn type_rule<E: GenericEvent>(&mut self, e: &E) -> bool {
+        use piston::input::{Button, Key};
+
+        let input = match self.rule_input.as_mut() {
+            Some(input) => input,
+            None => return false,
+        };
+        if let Some(typed) = e.text_args() {
+            input.extend(typed.chars().filter(|c| !c.is_control()));
+            return true;
+        }
+        match e.press_args() {
+            Some(Button::Keyboard(Key::Backspace)) => { input.pop(); },
+            Some(Button::Keyboard(Key::Return | Key::NumPadEnter)) => match Rule::parse(input) {
+                Ok(rule) => {
+                    // The board is refreshed as after any other key, since
+                    // sparse updates need its living cells found afresh.
+                    self.board.rule = rule;
+                    self.board.larger_than_life = None;
+                    self.board.refresh();
+                    self.dirty = true;
+                    self.rule_input = None;
+                    self.rule_error = None;
+                    println!("Rule: {}", rule);
+                },
+                Err(e) => self.rule_error = Some(e),
+            },
+            Some(Button::Keyboard(Key::Escape)) => {
+                self.rule_input = None;
+                self.rule_error = None;
+            },
+            Some(Button::Keyboard(_)) => {},
+            _ => return false,
+        }
+        true
+    }
+
+    /// [Change Speed]
+    ///
+    /// Multiplies or divides the target number of generations per second
+    /// by the given factor. Speeding up beyond `MAX_RATE` removes the
+    /// limit altogether, while slowing down from an unlimited speed starts
+    /// back at `MAX_RATE`. The speed can never drop below one generation
+    /// per second.
+    fn change_speed(&mut self, faster: bool, factor: f64) {
+        const MAX_RATE: u32 = 60;
+        let fastest = Duration::from_secs(1) / MAX_RATE;
+        let slowest = Duration::from_secs(1);
+
+        let interval = self.gen_interval.as_secs_f64();
+        self.gen_interval = match (faster, self.gen_interval.is_zero()) {
+            (true, true) => Duration::ZERO,
+            (true, false) if interval / factor < fastest.as_secs_f64() => Duration::ZERO,
+            (true, false) => Duration::from_secs_f64(interval / factor),
+            (false, true) => fastest,
+            (false, false) => Duration::from_secs_f64(interval * factor).min(slowest),
+        };
+    }
+
+    /// [Report Speed]
+    ///
+    /// Prints the target number of generations per second.
+    fn report_speed(&self) {
+        if self.gen_interval.is_zero() {
+            println!("Target speed: unlimited generations per second");
+        } else {
+            println!("Target speed: {:.1} generations per second", 1.0 / self.gen_interval.as_secs_f64());
+        }
+    }
+
+    /// [Step]
+    ///
+    /// Advances the game state by exactly one generation, under the
+    /// active rule and boundary settings, and ages every cell which
+    /// survived it.
+    fn step(&mut self) {
+        use rayon::prelude::*;
+
+        // Take initial time
+        let time_initial = Instant::now();
+        self.record();
+
+        // The board keeps the previous generation in its back buffer.
+        let old_cols = self.board.cols;
+        if let Some((left, top)) = self.board.step() {
+            self.follow_growth(old_cols, left, top);
+        }
+
+        // Surviving cells grow older, while births and deaths start over.
+        let (state, back) = (&self.board.state, &self.board.back);
+        self.age.par_iter_mut()
+            .enumerate()
+            .for_each(|(i, age)| {
+                *age = if state.get(i) && back.get(i) { age.saturating_add(1) } else { 0 };
+            });
+
+        // Cells which just died linger on screen for a few frames. This is
+        // purely for display, and plays no part in the rule.
+        if self.fading {
+            self.fade.par_iter_mut()
+                .enumerate()
+                .filter(|&(i, _)| back.get(i) && !state.get(i))
+                .for_each(|(_, fade)| *fade = FADE_FRAMES);
+        }
+
+        self.generation += 1;
+        self.generations += 1;
+        self.populations.push_back(self.board.population);
+        if self.populations.len() > GRAPH_LEN {
+            self.populations.pop_front();
+        }
+        self.dirty = true;
+        self.clusters = None;
+        self.record();
+        if self.following {
+            self.follow_centroid();
+        }
+        self.detect_cycle();
+
+        // The target generation pauses the game only the first time it is reached.
+        if self.pause_at == Some(self.generation) {
+            self.pause_at = None;
+            self.paused = true;
+            println!("Paused at generation {}", self.generation);
+        }
+
+        // For collecting CSV output:
+        let elapsed = time_initial.elapsed();
+        self.log_generation(elapsed);
+
+        // For demonstrative output, summarized rather than printed every generation:
+        if self.verbose {
+            self.timings.add(elapsed);
+            self.timings.report();
+        }
+
+        // Likewise for how the work was spread across threads.
+        if self.thread_stats_since.is_some_and(|since| since.elapsed() >= Duration::from_secs(1)) {
+            print_thread_stats();
+            self.thread_stats_since = Some(Instant::now());
+        }
+    }
+
+    /// [Detect Cycle]
+    ///
+    /// Compares the hash of the board against those of the last few
+    /// generations, to tell when the board has settled into a still life
+    /// (period 1) or an oscillator of at most `MAX_PERIOD` generations.
+    /// Reports each newly settled cycle, and pauses on it if asked to.
+    fn detect_cycle(&mut self) {
+        if let Some(period) = self.history.observe(&self.board.state) {
+            match period {
+                1 => println!("Still life detected at generation {}", self.generation),
+                p => println!("Oscillator of period {} detected at generation {}", p, self.generation),
+            }
+            if self.pause_on_cycle {
+                self.paused = true;
+            }
+        }
+    }
+
+    /// [Invalidate History]
+    ///
+    /// Forgets the generations cycles are found among, whenever the board
+    /// is changed other than by the simulation, so that the generations
+    /// following the change are never compared against those before it.
+    fn invalidate_history(&mut self) {
+        self.history.clear();
+    }
+
+    /// [Log Generation]
+    ///
+    /// Appends a row for the latest generation to the CSV log, if there
+    /// is one, holding the generation number, the time, the population
+    /// and how long the update took. Should writing fail, logging stops.
+    fn log_generation(&mut self, elapsed: Duration) {
+        // Rows are buffered, but flushed every so often in case of a crash.
+        const FLUSH_INTERVAL: u64 = 100;
+
+        let (generation, population) = (self.generation, self.board.population);
+        if let Some(log) = self.log.as_mut() {
+            let timestamp = chrono::Local::now().format("%Y-%m-%dT%H:%M:%S%.3f");
+            let result = writeln!(log, "{},{},{},{:.3}", generation, timestamp, population,
+                                  elapsed.as_secs_f64() * 1000.0)
+                .and_then(|_| if generation % FLUSH_INTERVAL == 0 { log.flush() } else { Ok(()) });
+
+            if let Err(e) = result {
+                println!("Could not write to the log ({}), so logging has stopped.", e);
+                self.log = None;
+            }
+        }
+    }
+
+    /// [Snapshot]
+    ///
+    /// Copies the game board as it currently stands.
+    fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            cols: self.board.cols,
+            rows: self.board.rows,
+            state: self.board.state.clone(),
+            colours: self.board.colours.clone(),
+            generation: self.generation
+        }
+    }
+
+    /// [Checkpoint]
+    ///
+    /// Records the board before a change to it, so that the change may be
+    /// undone. Only the latest `MAX_UNDO` changes are kept, or fewer once
+    /// the board is large, as `fit_snapshots` decides, and making a new
+    /// change forgets any which were undone.
+    fn checkpoint(&mut self) {
+        let snapshot = self.snapshot();
+        self.undo.push(snapshot);
+        self.redo.clear();
+        self.fit_snapshots();
+    }
+
+    /// [Snapshot Limit]
+    ///
+    /// Greatest number of snapshots of the board as it now stands which fit
+    /// within `SNAPSHOT_BUDGET`, though never more than `max` nor fewer than one.
+    fn snapshot_limit(&self, max: usize) -> usize {
+        let bytes = self.board.state.len().div_ceil(8) + self.board.colours.as_ref().map_or(0, Vec::len);
+        (SNAPSHOT_BUDGET / bytes.max(1)).max(1).min(max)
+    }
+
+    /// [Fit Snapshots]
+    ///
+    /// Forgets the oldest snapshots of the undo and redo histories and the
+    /// timeline beyond as many as `snapshot_limit` allows, which shrinks as
+    /// an expanding board grows. Each snapshot copies the whole board, so
+    /// at `board::MAX_SIDE` cells a side, each takes 2 MiB, or 18 MiB in a
+    /// coloured variant, and every full history would take several GiB.
+    fn fit_snapshots(&mut self) {
+        let undo = self.snapshot_limit(MAX_UNDO);
+        self.undo.drain(..self.undo.len().saturating_sub(undo));
+        self.redo.drain(..self.redo.len().saturating_sub(undo));
+
+        let timeline = self.snapshot_limit(self.timeline_len);
+        while self.timeline.len() > timeline {
+            self.timeline.pop_front();
+            self.timeline_pos = self.timeline_pos.saturating_sub(1);
+        }
+    }
+
+    /// [Record]
+    ///
+    /// Appends the board to the timeline, unless it is already the newest
+    /// generation there. Any generations after the one being shown are
+    /// forgotten first, as are any the board has since been reset to
+    /// before, or changed at. The oldest generations are forgotten once
+    /// the timeline is full, which happens sooner on large boards, as
+    /// `fit_snapshots` decides.
+    fn record(&mut self) {
+        if self.timeline_len == 0 {
+            return;
+        }
+
+        self.timeline.truncate(self.timeline_pos + 1);
+        if let Some(newest) = self.timeline.back() {
+            if newest.generation == self.generation && newest.state == self.board.state {
+                return;
+            }
+        }
+        while self.timeline.back().is_some_and(|newest| newest.generation >= self.generation) {
+            self.timeline.pop_back();
+        }
+
+        let snapshot = self.snapshot();
+        self.timeline.push_back(snapshot);
+        self.fit_snapshots();
+        self.timeline_pos = self.timeline.len() - 1;
+    }
+
+    /// [Scrub]
+    ///
+    /// Moves one generation backward or forward through the timeline,
+    /// restoring the board as it stood then. Moving forward from the
+    /// newest generation recorded computes a fresh one instead.
+    fn scrub(&mut self, forward: bool) {
+        if forward && self.timeline_pos + 1 >= self.timeline.len() {
+            self.step();
+            return;
+        }
+        if !forward && (self.timeline_pos == 0 || self.timeline.is_empty()) {
+            return;
+        }
+
+        self.timeline_pos = if forward { self.timeline_pos + 1 } else { self.timeline_pos - 1 };
+        let snapshot = self.timeline[self.timeline_pos].clone();
+        self.restore(snapshot);
+    }
+
+    /// [Undo]
+    ///
+    /// Returns the board to how it stood before the latest change, and
+    /// pauses the game so the restored board is not immediately advanced.
+    fn undo(&mut self) {
+        if let Some(snapshot) = self.undo.pop() {
+            let current = self.snapshot();
+            self.redo.push(current);
+            self.restore(snapshot);
+        }
+    }
+
+    /// [Redo]
+    ///
+    /// Reapplies the latest undone change, and pauses the game.
+    fn redo(&mut self) {
+        if let Some(snapshot) = self.redo.pop() {
+            let current = self.snapshot();
+            self.undo.push(current);
+            self.restore(snapshot);
+        }
+    }
+
+    /// [Restore]
+    ///
+    /// Overwrites the board with a snapshot and pauses the game. Ages
+    /// are not recorded, so every cell starts over. The snapshot may have
+    /// been taken before an expanding board grew, in which case the board
+    /// shrinks back to its size at the time.
+    fn restore(&mut self, snapshot: Snapshot) {
+        if (snapshot.cols, snapshot.rows) != (self.board.cols, self.board.rows) {
+            self.selection = None;
+        }
+        self.board.replace(snapshot.cols, snapshot.rows, snapshot.state);
+
+        // Cells keep the colours they had, should a coloured variant still be
+        // played. Those saved before it was begun all take the first colour.
+        if let (Some(_), Some(colours)) = (self.board.colours.as_ref(), snapshot.colours) {
+            self.board.colours = Some(colours);
+        }
+        self.generation = snapshot.generation;
+        self.age = vec![0; self.board.state.len()];
+        self.fade = vec![0; self.board.state.len()];
+        self.invalidate_history();
+        self.fit_texture();
+        self.paused = true;
+    }
+
+    /// [Follow Growth]
+    ///
+    /// Moves the ages and fading of every cell along with a board which
+    /// has grown from `old_cols` cells wide, with the previous board's
+    /// upper left corner now at the given column and row. The view and
+    /// selection move along too, so that nothing appears to move, while
+    /// the histories of snapshots shorten to make room for the larger board.
+    fn follow_growth(&mut self, old_cols: usize, left: usize, top: usize) {
+        fn regrid<T: Copy + Default>(old: &[T], old_cols: usize, board: &Board, left: usize, top: usize) -> Vec<T> {
+            let mut new = vec![T::default(); board.state.len()];
+            for (i, &value) in old.iter().enumerate() {
+                new[(i % old_cols + left) + (i / old_cols + top) * board.cols] = value;
+            }
+            new
+        }
+        self.age = regrid(&self.age, old_cols, &self.board, left, top);
+        self.fade = regrid(&self.fade, old_cols, &self.board, left, top);
+
+        self.offset[0] -= left as f64 * self.scale[0];
+        self.offset[1] -= top as f64 * self.scale[1];
+        if let Some([(x0, y0), (x1, y1)]) = self.selection {
+            self.selection = Some([(x0 + left, y0 + top), (x1 + left, y1 + top)]);
+        }
+        self.fit_snapshots();
+        self.fit_texture();
+    }
+
+    /// [Fit Texture]
+    ///
+    /// Recreates the pixel buffer and board texture whenever the board has
+    /// changed size. Should the new texture fail, rendering falls back to
+    /// drawing each living cell as a square.
+    fn fit_texture(&mut self) {
+        let size = self.board.state.len();
+        if self.pixels.len() != size * 4 {
+            self.pixels = vec![0; size * 4];
+            if self.texture.is_some() {
+                self.texture = board_texture(&self.pixels, self.board.cols, self.board.rows, self.filter)
+                    .map_err(|e| println!("Could not resize the board texture ({}), falling back to shapes.", e))
+                    .ok();
+            }
+        }
+        self.dirty = true;
+    }
+
+    /// [Cycle Colouring]
+    ///
+    /// Cycles from plain Life to Immigration, in which living cells come in
+    /// two colours, and each newborn cell takes the colour of most of the
+    /// neighbours it was born beside, then to QuadLife, its four colour
+    /// cousin, and back. Every living cell is given any of the variant's
+    /// colours at random.
+    fn cycle_colouring(&mut self) {
+        let colouring = match (self.board.colours.is_some(), self.board.colouring) {
+            (false, _) => Some(Colouring::Immigration),
+            (true, Colouring::Immigration) => Some(Colouring::QuadLife),
+            (true, Colouring::QuadLife) => None,
+        };
+        self.board.colours = colouring.map(|colouring| immigrant_colours(&self.board.state, &mut self.rng, colouring));
+        if let Some(colouring) = colouring {
+            self.board.colouring = colouring;
+        }
+        self.dirty = true;
+        println!("Colouring: {}", colouring.map_or(String::from("none"), |colouring| format!("{:?}", colouring)));
+    }
+
+    /// [Toggle Filter]
+    ///
+    /// Switches the board texture between nearest neighbour sampling,
+    /// which keeps cells crisp, and linear sampling, which smooths them.
+    /// The texture is recreated from the existing pixels with the new filter.
+    fn toggle_filter(&mut self) {
+        self.filter = match self.filter {
+            Filter::Nearest => Filter::Linear,
+            Filter::Linear => Filter::Nearest,
+        };
+        if self.texture.is_some() {
+            self.texture = board_texture(&self.pixels, self.board.cols, self.board.rows, self.filter)
+                .map_err(|e| println!("Could not recreate the board texture ({}), falling back to shapes.", e))
+                .ok();
+        }
+        println!("Texture filter: {:?}", self.filter);
+    }
+
+    /// [Save Slot]
+    ///
+    /// Quick-saves a copy of the board to the given slot, replacing any
+    /// board saved there before. Slots only last until the game closes.
+    fn save_slot(&mut self, slot: usize) {
+        self.slots[slot] = Some(self.snapshot());
+        println!("Saved slot {}", slot + 1);
+    }
+
+    /// [Load Slot]
+    ///
+    /// Overwrites the board with the one quick-saved to the given slot,
+    /// if any, and pauses the game. Loading can be undone.
+    fn load_slot(&mut self, slot: usize) {
+        match self.slots[slot].clone() {
+            Some(snapshot) => {
+                self.checkpoint();
+                self.restore(snapshot);
+                println!("Loaded slot {}", slot + 1);
+            },
+            None => println!("Slot {} is empty", slot + 1),
+        }
+    }
+
+    /// [Cell At]
+    ///
+    /// Converts the cursor position into the column and row of the cell
+    /// beneath it, accounting for the current pan offset and zoom scale.
+    /// The given position is that of the window's upper left corner.
+    /// Returns nothing if the cursor lies outside the board.
+    fn cell_at(&self, pos: [f64; 2]) -> Option<(usize, usize)> {
+        self.cell_under([self.cursor_pos[0] - pos[0], self.cursor_pos[1] - pos[1]])
+    }
+
+    /// [Cell Under]
+    ///
+    /// Converts a point within the window, relative to its upper left
+    /// corner, into the column and row of the cell beneath it. Returns
+    /// nothing if the point lies outside the board.
+    fn cell_under(&self, point: [f64; 2]) -> Option<(usize, usize)> {
+        // Find coordinates relative to the board's upper left corner, in cells.
+        let x = (point[0] - self.offset[0]) / self.scale[0];
+        let y = (point[1] - self.offset[1]) / self.scale[1];
+
+        // Check that coordinates are inside board boundaries.
+        if x >= 0.0 && x < self.board.cols as f64 && y >= 0.0 && y < self.board.rows as f64 {
+            Some((x as usize, y as usize))
+        } else {
+            None
+        }
+    }
+
+    /// [Clamped Cell Under]
+    ///
+    /// Converts a point within the window, relative to its upper left
+    /// corner, into the column and row of the nearest cell on the board,
+    /// so that points beyond its edges are moved onto it.
+    fn clamped_cell_under(&self, point: [f64; 2]) -> (usize, usize) {
+        let x = ((point[0] - self.offset[0]) / self.scale[0]).floor();
+        let y = ((point[1] - self.offset[1]) / self.scale[1]).floor();
+        (x.clamp(0.0, (self.board.cols - 1) as f64) as usize, y.clamp(0.0, (self.board.rows - 1) as f64) as usize)
+    }
+
+    /// [Selection Bounds]
+    ///
+    /// The column and row of the upper left corner of the selected
+    /// rectangle, along with its width and height in cells, if any.
+    fn selection_bounds(&self) -> Option<(usize, usize, usize, usize)> {
+        self.selection.map(|[(x0, y0), (x1, y1)]| {
+            (x0.min(x1), y0.min(y1), x0.abs_diff(x1) + 1, y0.abs_diff(y1) + 1)
+        })
+    }
+
+    /// [Clear Board]
+    ///
+    /// Kills every cell of the board, which can be undone. Boards with more
+    /// than `CONFIRM_POPULATION` living cells are only cleared by a second
+    /// press within `CONFIRM_WINDOW` of the first, so that a stray key
+    /// cannot wipe out a busy board.
+    fn clear_board(&mut self) {
+        const CONFIRM_POPULATION: usize = 1000;
+        const CONFIRM_WINDOW: Duration = Duration::from_secs(2);
+
+        let confirmed = self.clear_pressed.is_some_and(|pressed| pressed.elapsed() < CONFIRM_WINDOW);
+        if self.board.population > CONFIRM_POPULATION && !confirmed {
+            println!("Press C again to clear all {} living cells.", self.board.population);
+            self.clear_pressed = Some(Instant::now());
+            return;
+        }
+
+        self.clear_pressed = None;
+        self.checkpoint();
+        self.board.state.fill(false);
+        self.age.fill(0);
+        self.fade.fill(0);
+        self.invalidate_history();
+        self.generation = 0;
+    }
+
+    /// [Clear Selection]
+    ///
+    /// Kills every cell within the selected rectangle, and forgets the
+    /// selection.
+    fn clear_selection(&mut self) {
+        if let Some((left, top, width, height)) = self.selection_bounds() {
+            for y in top..top + height {
+                for x in left..left + width {
+                    self.board.state.set(x + y * self.board.cols, false);
+                    self.age[x + y * self.board.cols] = 0;
+                }
+            }
+            self.invalidate_history();
+        }
+        self.selection = None;
+    }
+
+    /// [Randomize Selection]
+    ///
+    /// Brings each cell within the selected rectangle to life with the
+    /// probability given by the density, leaving the rest of the board and
+    /// the selection itself untouched.
+    fn randomize_selection(&mut self) {
+        if let Some((left, top, width, height)) = self.selection_bounds() {
+            for y in top..top + height {
+                for x in left..left + width {
+                    self.board.state.set(x + y * self.board.cols, self.rng.gen_bool(self.density));
+                    self.age[x + y * self.board.cols] = 0;
+                }
+            }
+            self.invalidate_history();
+        }
+    }
+
+    /// [Copy Selection]
+    ///
+    /// Copies every cell within the selected rectangle to the clipboard.
+    fn copy_selection(&mut self) {
+        if let Some((left, top, width, height)) = self.selection_bounds() {
+            let cols = self.board.cols;
+            let indices: Vec<usize> = (top..top + height)
+                .flat_map(|y| (left..left + width).map(move |x| x + y * cols))
+                .collect();
+            let cells = indices.iter().map(|&i| self.board.state.get(i)).collect();
+            let colours = self.board.colours.as_ref().map(|colours| indices.iter().map(|&i| colours[i]).collect());
+            self.clipboard = Some(Clipboard { width, height, cells, colours });
+            println!("Copied {} x {} cells", width, height);
+        }
+    }
 
-/// [App]
-/// The App struct defines the Piston application and associated
-/// data. All fields within this structure are statically accessible
-/// from within the application's associated methods.
-///
-/// Fields:
-/// [gl] OpenGL graphics backend;
-/// [state] State of the game board as a flat array of booleans;
-/// [cursor_pos] Actively tracked location of the user's mouse cursor;
-/// [paused] Game state.
-pub struct App {
-    gl: GlGraphics,
-    state: [bool; SIZE],
-    cursor_pos: [f64; 2],
-    paused: bool
-}
+    /// [Paste]
+    ///
+    /// Writes the clipboard onto the board, with its upper left corner on
+    /// the cell under the cursor. Dead cells of the clipboard overwrite
+    /// the board, unless `merge` is set, in which case only its living
+    /// cells are written. Cells falling beyond the edges of the board are
+    /// clipped. Written cells keep the colours they were copied with, or
+    /// take the first when copied from plain Life. Pasting can be undone,
+    /// though with nothing copied, or the cursor off the board, the undo
+    /// history is left alone. The given position is that of the window's
+    /// upper left corner.
+    fn paste(&mut self, pos: [f64; 2], merge: bool) {
+        if self.clipboard.is_none() || self.cell_at(pos).is_none() {
+            return;
+        }
+        self.checkpoint();
 
-/// [App]
-/// Application related methods.
-impl App {
+        if let (Some(clipboard), Some((left, top))) = (self.clipboard.as_ref(), self.cell_at(pos)) {
+            let width = clipboard.width.min(self.board.cols - left);
+            let height = clipboard.height.min(self.board.rows - top);
+            for y in 0..height {
+                for x in 0..width {
+                    let alive = clipboard.cells[x + y * clipboard.width];
+                    if alive || !merge {
+                        let i = (left + x) + (top + y) * self.board.cols;
+                        self.board.state.set(i, alive);
+                        self.age[i] = 0;
+                        if let Some(colours) = self.board.colours.as_mut() {
+                            let copied = clipboard.colours.as_ref().map_or(1, |copied| copied[x + y * clipboard.width].max(1));
+                            colours[i] = if alive { copied } else { 0 };
+                        }
+                    }
+                }
+            }
+            self.invalidate_history();
+        }
+    }
 
-    /// [Render]
-    /// The render method is required by Piston in order to service
-    /// the application control-flow, using callbacks. The render
-    /// method is specifically meant to be where all calls to OpenGL
-    /// happen, and is meant to be called every frame.
+    /// [Paint]
     ///
-    /// This program implements the render method by checking each cell
-    /// of the game's state individually, and drawing the corresponding
-    /// pixel upon a blank background if the cell is alive.
+    /// Sets every cell within the brush radius of the given cell, measured
+    /// as the Chebyshev distance (so the brush is a square), to the given
+    /// state. The brush is clipped at the board's edges, rather than
+    /// wrapping around them.
+    fn paint(&mut self, cell_x: usize, cell_y: usize, alive: bool) {
+        let r = self.brush_radius;
+        for y in cell_y.saturating_sub(r)..=(cell_y + r).min(self.board.rows - 1) {
+            for x in cell_x.saturating_sub(r)..=(cell_x + r).min(self.board.cols - 1) {
+                self.board.state.set(x + y * self.board.cols, alive);
+                self.age[x + y * self.board.cols] = 0;
+            }
+        }
+        self.invalidate_history();
+    }
+
+    /// [Transform]
     ///
-    /// Being a Piston callback, its only parameters are itself,
-    /// and the Piston render arguments.
-    fn render(&mut self, args: &RenderArgs) {
+    /// Moves every living cell, along with its age and colour, and every
+    /// cell still fading away, to the column and row given by `map` for its
+    /// current column and row, and pauses the game. Cells mapped beyond the
+    /// edges of the board are clipped.
+    fn transform<F>(&mut self, map: F)
+        where F: Fn(isize, isize) -> (isize, isize) {
 
-        // Local constants:
-        const WHITE: [f32; 4] = [0.9, 0.9, 0.85, 1.0];
-        const BLACK: [f32; 4] = [0.6, 0.5, 0.52, 1.0];
+        let (cols, rows) = (self.board.cols as isize, self.board.rows as isize);
+        let mut age = vec![0; self.age.len()];
+        let mut fade = vec![0; self.fade.len()];
+        let mut colours = self.board.colours.as_ref().map(|colours| vec![0; colours.len()]);
+        self.board.back.fill(false);
+        for i in 0..self.board.state.len() {
+            let alive = self.board.state.get(i);
+            if !alive && self.fade[i] == 0 {
+                continue;
+            }
+            let (x, y) = map((i % self.board.cols) as isize, (i / self.board.cols) as isize);
+            if x >= 0 && x < cols && y >= 0 && y < rows {
+                let j = (x + y * cols) as usize;
+                fade[j] = self.fade[i];
+                if alive {
+                    self.board.back.set(j, true);
+                    age[j] = self.age[i];
+                    if let (Some(colours), Some(old)) = (colours.as_mut(), self.board.colours.as_ref()) {
+                        colours[j] = old[i];
+                    }
+                }
+            }
+        }
 
-        // Local variables:
-        let mut colour: [f32; 4] = WHITE;
+        std::mem::swap(&mut self.board.state, &mut self.board.back);
+        self.age = age;
+        self.fade = fade;
+        self.board.colours = colours;
+        self.invalidate_history();
+        self.paused = true;
+    }
 
-        // The following block of code will overwrite the OpenGL window with white.
-        self.gl.draw(args.viewport(), |c, gl| {
-            // Create the necessary components to draw with:
-            let background_fill =
-                rectangle::rectangle_by_corners(0.0, 0.0, WIDTH as f64, HEIGHT as f64);
-            let transform = c.transform;
+    /// [Flip Horizontally]
+    ///
+    /// Mirrors the board from left to right.
+    fn flip_horizontally(&mut self) {
+        let cols = self.board.cols as isize;
+        self.transform(|x, y| (cols - 1 - x, y));
+    }
 
-            // Collect all components and write to the screen.
-            rectangle(colour, background_fill, transform, gl);
+    /// [Flip Vertically]
+    ///
+    /// Mirrors the board from top to bottom.
+    fn flip_vertically(&mut self) {
+        let rows = self.board.rows as isize;
+        self.transform(|x, y| (x, rows - 1 - y));
+    }
+
+    /// [Rotate]
+    ///
+    /// Rotates the board a quarter turn clockwise about its centre. The
+    /// board keeps its dimensions, so unless it is square, cells rotated
+    /// beyond its edges are clipped.
+    fn rotate(&mut self) {
+        let (cols, rows) = (self.board.cols as isize, self.board.rows as isize);
+
+        // Measured in half cells from the centre, every cell lies on a whole
+        // number, whatever the parity of the board's dimensions.
+        self.transform(|x, y| {
+            let (dx, dy) = (2 * x + 1 - cols, 2 * y + 1 - rows);
+            ((cols - 1 - dy).div_euclid(2), (rows - 1 + dx).div_euclid(2))
         });
+    }
 
-        // Begin iterating over all individual cells within the state array.
-        colour = BLACK;
-        for y in 0usize..(ROWS) {
-            for x in 0usize..(COLS) {
-                // We only want to draw a square to OpenGL if the cell is alive:
-                if self.state[x + y * COLS] {
-
-                    // We draw the living cell as a square, which is a data structure
-                    // with 3 floating point values representing position and size.
-                    let square = rectangle::square((x * SCALE) as f64, (y * SCALE) as f64, SCALE as f64);
-                    self.gl.draw(args.viewport(), |c, gl| {
-                        // Must update the current OpenGL transformation
-                        // before drawing the pixel.
-                        let transform = c.transform;
-                        rectangle(colour, square, transform, gl);
-                    });
-                }
+    /// [Stamp At Cursor]
+    ///
+    /// Writes a pattern onto the board, centred on the cell under the
+    /// cursor. Cells falling beyond the edges of the board are clipped,
+    /// rather than wrapped around. The given position is that of the
+    /// window's upper left corner.
+    fn stamp_at_cursor(&mut self, pos: [f64; 2], pattern: &Pattern) {
+        if let Some(cell) = self.cell_at(pos) {
+            let (left, top) = stamp_origin(cell, pattern);
+            stamp(&mut self.board.state, self.board.colours.as_deref_mut(), self.board.cols, self.board.rows,
+                  pattern, left, top, self.blend);
+            self.invalidate_history();
+        }
+    }
+
+    /// [Next Pattern]
+    ///
+    /// Moves on to placing the next of the loaded patterns with the mouse,
+    /// or back to drawing after the last of them.
+    fn next_pattern(&mut self) {
+        self.placing = match self.placing {
+            None if !self.patterns.is_empty() => Some(0),
+            Some(index) if index + 1 < self.patterns.len() => Some(index + 1),
+            _ => None,
+        };
+        match self.placing {
+            Some(index) => println!("Placing: {}", self.patterns[index].0),
+            None => println!("Drawing"),
+        }
+    }
+
+    /// [Paint Line]
+    ///
+    /// Paints every cell the cursor passed over while moving between two
+    /// points within the window, relative to its upper left corner. The
+    /// points in between are sampled at least once per cell, so that fast
+    /// drags do not leave gaps.
+    fn paint_line(&mut self, from: [f64; 2], to: [f64; 2], alive: bool) {
+        let cells = ((to[0] - from[0]) / self.scale[0]).abs().max(((to[1] - from[1]) / self.scale[1]).abs());
+        let steps = cells.ceil().max(1.0) as usize;
+
+        for step in 0..=steps {
+            let t = step as f64 / steps as f64;
+            let point = [from[0] + (to[0] - from[0]) * t, from[1] + (to[1] - from[1]) * t];
+            if let Some((cell_x, cell_y)) = self.cell_under(point) {
+                self.paint(cell_x, cell_y, alive);
             }
         }
     }
-    
-    /// [Update]
+
+    /// [Resize Brush]
     ///
-    /// The update method is required by Piston in order to service
-    /// the application logic (as opposed to rendering) using callbacks.
-    /// The update method contains user-defined logic which does not
-    /// necessarily have to do with drawing to OpenGL.
+    /// Grows or shrinks the brush radius by one cell, between a single
+    /// cell and `MAX_BRUSH_RADIUS`.
+    fn resize_brush(&mut self, grow: bool) {
+        const MAX_BRUSH_RADIUS: usize = 32;
+
+        self.brush_radius = if grow {
+            (self.brush_radius + 1).min(MAX_BRUSH_RADIUS)
+        } else {
+            self.brush_radius.saturating_sub(1)
+        };
+        println!("Brush radius: {}", self.brush_radius);
+    }
+
+    /// [Count Clusters]
+    ///
+    /// Counts the separate clusters of living cells on the board, and
+    /// reports how many there are along with the size of the largest,
+    /// both in the console and alongside the statistics until the next
+    /// generation.
+    fn count_clusters(&mut self) {
+        let sizes = self.board.components();
+        let largest = sizes.first().copied().unwrap_or(0);
+        println!("Clusters: {}, the largest of {} cells", sizes.len(), largest);
+        self.clusters = Some((sizes.len(), largest));
+    }
+
+    /// [Tile]
     ///
-    /// Therefore, this method updates the game state for the current
-    /// Game of Life instance by checking each individual cell from the
-    /// previous state, and updating the focused cell for the next state
-    /// accordingly. This method has been parallelized using the Rayon
-    /// crate, in order to allow each cell to be analyzed by the next
-    /// available parallel thread.
+    /// Clears the board and fills it with copies of the pattern being
+    /// placed, or a blinker if there is none, in a regular grid starting
+    /// from the upper left corner, with `tile_spacing` dead cells between
+    /// neighbouring copies. Copies overhanging the right and bottom edges
+    /// are clipped.
+    fn tile(&mut self) {
+        let pattern = match self.placing {
+            Some(index) => self.patterns[index].1.clone(),
+            None => Pattern::from_offsets(pattern::BLINKER),
+        };
+
+        tile_pattern(&mut self.board.state, self.board.colours.as_deref_mut(), self.board.cols, self.board.rows,
+                     &pattern, self.tile_spacing);
+        self.age.fill(0);
+        self.invalidate_history();
+        self.generation = 0;
+    }
+
+    /// [Change Tile Spacing]
     ///
-    /// Being a Piston callback, its only parameters are itself,
-    /// and the Piston update arguments.
-    fn update(&mut self, _args: &UpdateArgs) {
-        // Only update frames if the game is un-paused.
-        if !self.paused {
-
-            // Copy the previous state for later reference. This
-            // is necessary, as each cell's update relies on the
-            // previous state of the board.
-            let previous_state: [bool; SIZE] = self.state;
-            use rayon::prelude::*;
-
-            // Take initial time
-            let time_initial = Instant::now();
-
-
-            // Rayon parallel iterator:
-            // .enumerate() -> Provides us with an index for each iterated value.
-            //                 this is necessary for the Game of Life.
-            // .for_each()  -> Iterates over each value of the parallel iterator.
-            //                 Provides the index of the focused value, and a
-            //                 reference to the focused value itself within its
-            //                 closure (straight brackets).
-            self.state.par_iter_mut()
-                .enumerate()
-                .for_each( |(i, pixel)| {
-
-                    // Observe state of neighbouring cells:
-                    let mut neighbour = 0;
-
-                    neighbour += previous_state[(SIZE + i - 1 - COLS) % SIZE] as i32;
-                    neighbour += previous_state[(SIZE + i - COLS) % SIZE] as i32;
-                    neighbour += previous_state[(SIZE + i + 1 - COLS) % SIZE] as i32;
-                    neighbour += previous_state[(SIZE + i - 1) % SIZE] as i32;
-                    neighbour += previous_state[(SIZE + i + 1) % SIZE] as i32;
-                    neighbour += previous_state[(SIZE + i - 1 + COLS) % SIZE] as i32;
-                    neighbour += previous_state[(SIZE + i + COLS) % SIZE] as i32;
-                    neighbour += previous_state[(SIZE + i + 1 + COLS) % SIZE] as i32;
-
-                    // Based on current state, change to new state!
-                    if previous_state[i] {
-                        if neighbour < 2 || neighbour > 3 {
-                            *pixel = !previous_state[i];
-                        }
-                    } else if neighbour == 3 {
-                        *pixel = !previous_state[i];
-                    } else {
-                        *pixel = previous_state[i];
-                    }
-                });
+    /// Widens or narrows the gap left between tiles by one cell, down to
+    /// tiles touching one another.
+    fn change_tile_spacing(&mut self, wider: bool) {
+        self.tile_spacing = if wider { self.tile_spacing + 1 } else { self.tile_spacing.saturating_sub(1) };
+        println!("Tile spacing: {}", self.tile_spacing);
+    }
+
+    /// [Zoom]
+    ///
+    /// Multiplies the zoom scale by the given factor, keeping the point
+    /// of the board beneath the cursor in place. The factor is clamped,
+    /// so that the board can neither vanish nor grow unreasonably large,
+    /// and cells keep their shape.
+    fn zoom(&mut self, factor: f64) {
+        const MIN_SCALE: f64 = 0.5;
+        const MAX_SCALE: f64 = 64.0;
+
+        let (smallest, largest) = (self.scale[0].min(self.scale[1]), self.scale[0].max(self.scale[1]));
+        let factor = factor.min(MAX_SCALE / largest).max(MIN_SCALE / smallest);
+        for axis in 0..2 {
+            let anchor = self.cursor_pos[axis];
+            self.offset[axis] = anchor - (anchor - self.offset[axis]) * factor;
+            self.scale[axis] *= factor;
+        }
+    }
+
+    /// [Follow Centroid]
+    ///
+    /// Pans the view so that the centroid of every living cell lies at
+    /// the centre of the window. An empty board leaves the view where it
+    /// is.
+    fn follow_centroid(&mut self) {
+        if let Some((x, y)) = self.board.centroid() {
+            self.offset[0] = self.window_size[0] / 2.0 - (x + 0.5) * self.scale[0];
+            self.offset[1] = self.window_size[1] / 2.0 - (y + 0.5) * self.scale[1];
+        }
+    }
+
+    /// [Reset View]
+    ///
+    /// Undoes any panning and zooming, returning cells to their configured
+    /// size, shrunk or grown along with the window, so that the whole board
+    /// is framed from its upper left corner again. The board itself is left
+    /// as it is, though the view stops following its living cells.
+    fn reset_view(&mut self) {
+        let cells = [self.board.cols, self.board.rows];
+        let factor = (0..2)
+            .map(|axis| self.window_size[axis] / (cells[axis] * self.cell_size[axis]) as f64)
+            .fold(f64::INFINITY, f64::min);
+        self.offset = [0.0, 0.0];
+        self.scale = self.cell_size.map(|size| size as f64 * factor);
+        self.following = false;
+    }
+
+    /// [Resize]
+    ///
+    /// Rescales the view of the board along with the window, so that the
+    /// board keeps its dimensions in cells while filling as much of the
+    /// resized window as it did before.
+    fn resize(&mut self, window_size: [f64; 2]) {
+        let factor = (window_size[0] / self.window_size[0]).min(window_size[1] / self.window_size[1]);
+        if factor.is_finite() && factor > 0.0 {
+            self.offset = [self.offset[0] * factor, self.offset[1] * factor];
+            self.scale = self.scale.map(|scale| scale * factor);
+        }
+        self.window_size = window_size;
+    }
+
+    /// [Print Board]
+    ///
+    /// Prints the living cells of the board to the console as text, with
+    /// `#` for living cells and `.` for dead ones, trimmed to their
+    /// bounding box. Regions larger than `MAX_PRINT_SIDE` cells in either
+    /// dimension are cut down to their upper left corner, with a warning.
+    fn print_board(&self) {
+        const MAX_PRINT_SIDE: usize = 200;
+
+        let pattern = match live_pattern(&self.board.state, self.board.cols) {
+            Some(pattern) => pattern,
+            None => {
+                println!("The board is empty, so there is nothing to print.");
+                return;
+            }
+        };
+
+        let (width, height) = (pattern.width.min(MAX_PRINT_SIDE), pattern.height.min(MAX_PRINT_SIDE));
+        let mut lines = vec![vec![b'.'; width]; height];
+        for &(x, y) in pattern.cells.iter().filter(|&&(x, y)| x < width && y < height) {
+            lines[y][x] = b'#';
+        }
+
+        println!("Living cells at generation {} ({} x {}):", self.generation, pattern.width, pattern.height);
+        for line in lines {
+            println!("{}", String::from_utf8(line).unwrap());
+        }
+        if (width, height) != (pattern.width, pattern.height) {
+            println!("Warning: only the upper left {} x {} cells were printed.", width, height);
+        }
+    }
+
+    /// [Save RLE]
+    ///
+    /// Writes the living cells of the board, trimmed to their bounding
+    /// box, to a timestamped RLE file within the working directory.
+    fn save_rle(&self) {
+        let pattern = match live_pattern(&self.board.state, self.board.cols) {
+            Some(pattern) => pattern,
+            None => {
+                println!("The board is empty, so there is nothing to save.");
+                return;
+            }
+        };
+
+        let filename = chrono::Local::now().format("board_%Y-%m-%d_%H-%M-%S.rle").to_string();
+        match std::fs::write(&filename, pattern::encode_rle(&pattern)) {
+            Ok(()) => println!("Saved the board to {}", filename),
+            Err(e) => eprintln!("Could not save the board to {}: {}", filename, e),
+        }
+    }
+
+    /// [Save SVG]
+    ///
+    /// Writes the living cells of the board, trimmed to their bounding box,
+    /// to a timestamped SVG file within the working directory, with each
+    /// cell a square in its current colour at its column and row. Unlike a
+    /// screenshot, the image stays crisp at any size.
+    fn save_svg(&self) {
+        let (left, top, right, bottom) = match self.board.bounding_box() {
+            Some(bounds) => bounds,
+            None => {
+                println!("The board is empty, so there is nothing to save.");
+                return;
+            }
+        };
+
+        let (cols, colour_of) = (self.board.cols, self.colour_of());
+        let cells: Vec<(usize, usize, [f32; 4])> = self.board.state.alive_indices()
+            .map(|i| (i % cols, i / cols, colour_of(true, self.age[i], 0, self.immigrant(i))))
+            .collect();
+        let svg = encode_svg(&cells, (left, top, right - left + 1, bottom - top + 1), self.cell_size, THEMES[self.theme].background);
+
+        let filename = chrono::Local::now().format("board_%Y-%m-%d_%H-%M-%S.svg").to_string();
+        match std::fs::write(&filename, svg) {
+            Ok(()) => println!("Saved the board to {}", filename),
+            Err(e) => eprintln!("Could not save the board to {}: {}", filename, e),
+        }
+    }
+
+    /// [Save Config]
+    ///
+    /// Writes the current settings to the session's config file, from
+    /// which later runs may load them with `--config`.
+    fn save_config(&self) {
+        let config = Config {
+            threads: Some(self.threads),
+            density: Some(self.density),
+            rule: Some(self.board.rule.to_string()),
+            boundary: Some(format!("{:?}", self.board.boundary).to_ascii_lowercase()),
+            theme: Some(THEMES[self.theme].name.to_string()),
+            scale: Some(self.cell_size[0]).filter(|_| self.cell_size[0] == self.cell_size[1]),
+            scale_x: Some(self.cell_size[0]).filter(|_| self.cell_size[0] != self.cell_size[1]),
+            scale_y: Some(self.cell_size[1]).filter(|_| self.cell_size[0] != self.cell_size[1]),
+        };
+        match config.save(&self.config_path) {
+            Ok(()) => println!("Saved the settings to {}", self.config_path),
+            Err(e) => eprintln!("{}", e),
+        }
+    }
 
-            // For collecting CSV output:
-            //print!("{},", now.elapsed().as_millis());
+    /// [Save Session]
+    ///
+    /// Writes the complete state of the simulation, from the board and
+    /// generation to the rule, edges and random number generator, to a
+    /// timestamped `.gol` file within the working directory, which a
+    /// later run may carry on from with `--resume`. The generator cannot
+    /// be written out as it is, so it is reseeded from itself first, and
+    /// the new seed saved instead.
+    fn save_session(&mut self) {
+        let seed = self.rng.gen();
+        self.rng = StdRng::seed_from_u64(seed);
 
-            // For demonstrative output:
-            println!("Rendered in {}ms", time_initial.elapsed().as_millis());
+        let session = Session {
+            cols: self.board.cols,
+            rows: self.board.rows,
+            words: self.board.state.words().to_vec(),
+            generation: self.generation,
+            rule: self.board.rule.to_string(),
+            larger_than_life: self.board.larger_than_life.map(|rule| rule.to_string()),
+            boundary: format!("{:?}", self.board.boundary).to_ascii_lowercase(),
+            neighbourhood: format!("{:?}", self.board.neighbourhood).to_ascii_lowercase(),
+            asynchronous: self.board.asynchronous,
+            expanding: self.board.expanding,
+            density: self.density,
+            seed,
+        };
+        let filename = chrono::Local::now().format("session_%Y-%m-%d_%H-%M-%S.gol").to_string();
+        match session.save(&filename) {
+            Ok(()) => println!("Saved the session to {}", filename),
+            Err(e) => eprintln!("{}", e),
         }
     }
 
+    /// [Save PNG]
+    ///
+    /// Writes a screenshot of the board, with each cell drawn as a
+    /// square the size of a cell before zooming, in its current colour, to a timestamped
+    /// PNG file within the working directory. Only the colours are read
+    /// here, while scaling and encoding the image happen on a separate
+    /// thread, so as not to stall the event loop.
+    fn save_png(&self) {
+        let colour_of = self.colour_of();
+        let cells = image::RgbaImage::from_fn(self.board.cols as u32, self.board.rows as u32, |x, y| {
+            let i = x as usize + y as usize * self.board.cols;
+            image::Rgba(to_rgba8(colour_of(self.board.state.get(i), self.age[i], self.fade[i], self.immigrant(i))))
+        });
+
+        let filename = chrono::Local::now().format("board_%Y-%m-%d_%H-%M-%S.png").to_string();
+        let (width, height) = ((self.board.cols * self.cell_size[0]) as u32, (self.board.rows * self.cell_size[1]) as u32);
+        std::thread::spawn(move || {
+            let screenshot = image::imageops::resize(&cells, width, height, image::imageops::FilterType::Nearest);
+            match screenshot.save(&filename) {
+                Ok(()) => println!("Saved a screenshot to {}", filename),
+                Err(e) => eprintln!("Could not save a screenshot to {}: {}", filename, e),
+            }
+        });
+    }
+
     /// [Event]
     ///
     /// The event method is required by Piston in order to service
@@ -206,46 +2624,361 @@ impl App {
     /// and support for mouse interaction. Such input is necessary
     /// for clearing the board, regenerating the board, and drawing
     /// directly to the board.
-    
     fn event<E: GenericEvent>(&mut self, pos: [f64; 2], e: &E) {
         use piston::input::{Button, Key, MouseButton};
 
+        // Distance the cursor must move, in pixels, for a press to paint
+        // rather than click.
+        const DRAG_THRESHOLD: f64 = 4.0;
+
         // Mouse Function Added!
         // Left Click to change the flip the state of a cell
-        if let Some(pos) = e.mouse_cursor_args() {
-            self.cursor_pos = pos;
+        // Right Click to kill a cell
+        // Left Drag to draw living cells
+        // Shift + Left Drag to select a rectangle of cells
+        // Right Drag to erase cells
+        // Middle Drag to pan the board
+        // Scroll to zoom towards the cursor
+        // While a rule is being typed in, only panning and zooming work.
+        let typing = self.rule_input.is_some();
+        if let Some(cursor) = e.mouse_cursor_args() {
+            if self.panning {
+                self.offset[0] += cursor[0] - self.cursor_pos[0];
+                self.offset[1] += cursor[1] - self.cursor_pos[1];
+            }
+            if self.drawing && !typing {
+                let mut from = [self.cursor_pos[0] - pos[0], self.cursor_pos[1] - pos[1]];
+                let moved = (cursor[0] - self.press_cursor[0]).hypot(cursor[1] - self.press_cursor[1]);
+                if !self.dragging && moved > DRAG_THRESHOLD {
+                    // Each stroke starts where the button was pressed, and is
+                    // undone as a whole.
+                    self.dragging = true;
+                    self.checkpoint();
+                    from = [self.press_cursor[0] - pos[0], self.press_cursor[1] - pos[1]];
+                }
+                if self.dragging {
+                    let to = [cursor[0] - pos[0], cursor[1] - pos[1]];
+                    self.paint_line(from, to, self.drawing_alive);
+                }
+            }
+            if let (true, false, Some([anchor, _])) = (self.selecting, typing, self.selection) {
+                let corner = self.clamped_cell_under([cursor[0] - pos[0], cursor[1] - pos[1]]);
+                self.selection = Some([anchor, corner]);
+            }
+            self.cursor_pos = cursor;
+        }
+
+        // Clicking the minimap recentres the view there, rather than drawing.
+        let on_minimap = e.press_args() == Some(Button::Mouse(MouseButton::Left)) && !typing
+            && self.recentre_on_minimap([self.cursor_pos[0] - pos[0], self.cursor_pos[1] - pos[1]]);
+
+        // Shift + Left Drag to select a rectangle of cells, which any other
+        // click forgets.
+        if let (Some(Button::Mouse(MouseButton::Left)), false, false) = (e.press_args(), on_minimap, typing) {
+            match self.cell_at(pos) {
+                Some(cell) if self.shift => {
+                    self.selection = Some([cell, cell]);
+                    self.selecting = true;
+                },
+                _ => self.selection = None,
+            }
+        }
+        if let Some(Button::Mouse(MouseButton::Left)) = e.release_args() {
+            self.selecting = false;
+        }
+
+        // Presses made while selecting or typing in a rule never draw, and
+        // while placing a pattern, left clicks stamp it instead.
+        let press = if self.selecting || on_minimap || typing { None } else { e.press_args() };
+        let press = match (press, self.placing) {
+            (Some(Button::Mouse(MouseButton::Left)), Some(index)) => {
+                self.checkpoint();
+                let pattern = self.patterns[index].1.clone();
+                self.stamp_at_cursor(pos, &pattern);
+                None
+            },
+            _ => press,
+        };
+        if let Some(Button::Mouse(button @ (MouseButton::Left | MouseButton::Right))) = press {
+            self.drawing = true;
+            self.dragging = false;
+            self.drawing_alive = button == MouseButton::Left;
+            self.press_cursor = self.cursor_pos;
         }
-        if let Some(Button::Mouse(MouseButton::Left)) = e.press_args() {
-            // Find coordinates relative to upper left corner.
-            let x = self.cursor_pos[0] - pos[0];
-            let y = self.cursor_pos[1] - pos[1];
-            
-            // Check that coordinates are inside board boundaries.
-            if x >= 0.0 && x <= WIDTH as f64 && y >= 0.0 && y <= HEIGHT as f64 {
-                // Compute the cell position.
-                let cell_x = (x / SCALE as f64) as usize;
-                let cell_y = (y / SCALE as f64) as usize;
-                // Flip the state of that cell
-                self.state[cell_x + cell_y * COLS] = !self.state[cell_x + cell_y * COLS];
+
+        // A press released without dragging clicks the cell it was made on.
+        let released = matches!(e.release_args(), Some(Button::Mouse(MouseButton::Left | MouseButton::Right)));
+        if released && self.drawing && !self.dragging && !typing {
+            let point = [self.press_cursor[0] - pos[0], self.press_cursor[1] - pos[1]];
+            if let Some((cell_x, cell_y)) = self.cell_under(point) {
+                self.checkpoint();
+                if !self.drawing_alive {
+                    // Kill every cell under the brush, whatever its state
+                    self.paint(cell_x, cell_y, false);
+                } else if self.brush_radius == 0 {
+                    // Flip the state of that cell
+                    let i = cell_x + cell_y * self.board.cols;
+                    self.board.state.set(i, !self.board.state.get(i));
+                    self.age[i] = 0;
+                    self.invalidate_history();
+                } else {
+                    // Bring every cell under the brush to life
+                    self.paint(cell_x, cell_y, true);
+                }
             }
         }
+        if released {
+            self.drawing = false;
+            self.dragging = false;
+        }
+        if let Some(Button::Mouse(MouseButton::Middle)) = e.press_args() {
+            self.panning = true;
+        }
+        if let Some(Button::Mouse(MouseButton::Middle)) = e.release_args() {
+            self.panning = false;
+        }
+        if let Some([_, scroll]) = e.mouse_scroll_args() {
+            self.zoom(1.25f64.powf(scroll));
+        }
+
+        // Resizing the window rescales the view, rather than the board.
+        if let Some(args) = e.resize_args() {
+            self.resize(args.window_size);
+        }
+
+        // Keys type in the new rule instead, while one is being typed in.
+        if self.type_rule(e) {
+            return;
+        }
 
         // Key Functions
         // Space:   pause the game
-        // C:       cull all living cells
-        // R:       create a random starting board
+        // C:       cull all living cells, or only those within the selection
+        // R:       create a random starting board, or randomize only the selection
+        // Shift+R: create a random starting board, mirrored across both axes
+        // G:       toggle the grid overlay
+        // Ctrl+G:  toggle the population graph
+        // A:       toggle colouring cells by age
+        // W:       cycle between wrapping around, dead and reflective edges
+        // Ctrl+W:  toggle marking the edges living cells wrap around
+        // F:       toggle fading out dying cells
+        // Ctrl+F:  switch between crisp and smooth texture filtering
+        // K:       cycle through the colour themes
+        // L:       toggle keeping the view centred on the living cells
+        // E:       cycle the shape living cells are drawn as
+        // X:       toggle tinting cells by their numbers of living neighbours
+        // Tab:     toggle the minimap
+        // Q:       cycle through the loaded patterns to place with the mouse
+        // Shift+Q: cycle how placed patterns blend with the board
+        // M:       cycle between the Moore, von Neumann and diagonal neighbourhoods
+        // J:       pause or resume drawing the board, while generations carry on
+        // T:       cycle through the preset rules
+        // U:       switch between synchronous and asynchronous updates
+        // N:       advance a single generation while paused
+        // ,/.:     scrub backward or forward through recent generations while paused
+        // +/-:     raise or lower the target simulation speed
+        // [/]:     shrink or grow the brush
+        // D:       count the separate clusters of living cells
+        // Ctrl+D:  print the living cells to the console
+        // B:       tile the board with the pattern being placed, or blinkers
+        // Ctrl+B:  toggle outlining the bounding box of the living cells
+        // Ctrl+[/]: narrow or widen the spacing between tiles
+        // S:       save the board to an RLE file
+        // Shift+S: save the whole session, to be resumed later
+        // Ctrl+S:  save the settings to the config file
+        // P:       save a screenshot to a PNG file
+        // Ctrl+P:  save the living cells to an SVG file
+        // Home/0:  reset the pan and zoom
+        // 1/2/3:   stamp a glider, blinker or Gosper glider gun at the cursor
+        // H/V:     flip the board horizontally or vertically
+        // O:       rotate the board a quarter turn clockwise
+        // Shift+O: rotate the pattern being placed a quarter turn clockwise
+        // I:       invert every cell of the board
+        // Ctrl+I:  cycle between plain Life, Immigration and QuadLife
+        // Ctrl+Z:  undo the latest change to the board
+        // Ctrl+Y:  redo the latest undone change
+        // Ctrl+C:  copy the selection
+        // Ctrl+V:  paste at the cursor, or merge it in with Shift held
+        // Shift+1-9: quick-save the board to a slot
+        // Ctrl+1-9:  quick-load the board from a slot
+        // Ctrl+R:  type in a new rule, starting from the active one
         if let Some(Button::Keyboard(key)) = e.press_args() {
-                let mut i = 0;
+            let plain = !self.shift && !self.ctrl;
+            if let Some(slot) = slot_of(key) {
+                if self.shift {
+                    self.save_slot(slot);
+                } else if self.ctrl {
+                    self.load_slot(slot);
+                }
+            }
                 match key {
                     Key::Space => self.paused = !self.paused,
-                    Key::C => self.state = [false; SIZE],
-                    Key::R => while i < SIZE { self.state[i] = rand::random(); i = i + 1; },
+                    Key::C if self.ctrl => self.copy_selection(),
+                    Key::V if self.ctrl => self.paste(pos, self.shift),
+                    Key::C if self.selection.is_some() => { self.checkpoint(); self.clear_selection(); },
+                    Key::C => self.clear_board(),
+                    Key::R if self.ctrl => self.rule_input = Some(self.board.rule.to_string()),
+                    Key::R if self.shift => {
+                        self.checkpoint();
+                        symmetric_randomize(&mut self.board.state, self.board.cols, self.board.rows, &mut self.rng, self.density);
+                        self.age.fill(0);
+                        self.invalidate_history();
+                        self.generation = 0;
+                    },
+                    Key::R if self.selection.is_some() => { self.checkpoint(); self.randomize_selection(); },
+                    Key::R => {
+                        self.checkpoint();
+                        randomize(&mut self.board.state, &mut self.rng, self.density);
+                        if self.board.colours.is_some() {
+                            self.board.colours = Some(immigrant_colours(&self.board.state, &mut self.rng, self.board.colouring));
+                        }
+                        self.age.fill(0);
+                        self.invalidate_history();
+                        self.generation = 0;
+                    },
+                    Key::G if self.ctrl => self.graph = !self.graph,
+                    Key::G => self.grid = !self.grid,
+                    Key::A => self.age_colours = !self.age_colours,
+                    Key::W if self.ctrl => self.edges_shown = !self.edges_shown,
+                    Key::W => {
+                        self.board.boundary = self.board.boundary.next();
+                        println!("Boundary: {:?}", self.board.boundary);
+                    },
+                    Key::M => self.cycle_neighbourhood(),
+                    Key::J => {
+                        self.frozen = !self.frozen;
+                        self.dirty = true;
+                        println!("Rendering {}", if self.frozen { "paused" } else { "resumed" });
+                    },
+                    Key::T => self.next_preset(),
+                    Key::U => {
+                        self.board.asynchronous = !self.board.asynchronous;
+                        println!("Update: {}", if self.board.asynchronous { "asynchronous" } else { "synchronous" });
+                    },
+                    Key::Tab => self.minimap = !self.minimap,
+                    Key::Q if self.shift => {
+                        self.blend = self.blend.next();
+                        println!("Blend: {:?}", self.blend);
+                    },
+                    Key::Q => self.next_pattern(),
+                    Key::X => {
+                        self.board.counts = match self.board.counts {
+                            Some(_) => None,
+                            None => Some(vec![0; self.board.state.len()]),
+                        };
+                        self.dirty = true;
+                    },
+                    Key::E => {
+                        self.cell_shape = self.cell_shape.next();
+                        self.dirty = true;
+                        println!("Cell shape: {:?}", self.cell_shape);
+                    },
+                    Key::L => {
+                        self.following = !self.following;
+                        if self.following {
+                            self.follow_centroid();
+                        }
+                    },
+                    Key::K => {
+                        self.theme = (self.theme + 1) % THEMES.len();
+                        println!("Theme: {}", THEMES[self.theme].name);
+                    },
+                    Key::F if self.ctrl => self.toggle_filter(),
+                    Key::F => { self.fading = !self.fading; self.fade.fill(0); },
+                    Key::N if self.paused => { self.checkpoint(); self.step(); },
+                    Key::Comma if self.paused => self.scrub(false),
+                    Key::Period if self.paused => self.scrub(true),
+                    Key::Equals | Key::Plus | Key::NumPadPlus => self.press_speed(true),
+                    Key::Minus | Key::NumPadMinus => self.press_speed(false),
+                    Key::D if self.ctrl => self.print_board(),
+                    Key::D => self.count_clusters(),
+                    Key::B if self.ctrl => {
+                        self.bounds_shown = !self.bounds_shown;
+                        self.bounds = self.board.bounding_box();
+                    },
+                    Key::B => { self.checkpoint(); self.tile(); },
+                    Key::LeftBracket if self.ctrl => self.change_tile_spacing(false),
+                    Key::RightBracket if self.ctrl => self.change_tile_spacing(true),
+                    Key::LeftBracket => self.resize_brush(false),
+                    Key::RightBracket => self.resize_brush(true),
+                    Key::S if self.ctrl => self.save_config(),
+                    Key::S if self.shift => self.save_session(),
+                    Key::S => self.save_rle(),
+                    Key::P if self.ctrl => self.save_svg(),
+                    Key::P => self.save_png(),
+                    Key::Home => self.reset_view(),
+                    Key::D0 if plain => self.reset_view(),
+                    Key::D1 if plain => { self.checkpoint(); self.stamp_at_cursor(pos, &Pattern::from_offsets(pattern::GLIDER)); },
+                    Key::D2 if plain => { self.checkpoint(); self.stamp_at_cursor(pos, &Pattern::from_offsets(pattern::BLINKER)); },
+                    Key::D3 if plain => { self.checkpoint(); self.stamp_at_cursor(pos, &Pattern::from_offsets(pattern::GOSPER_GLIDER_GUN)); },
+                    Key::H => { self.checkpoint(); self.flip_horizontally(); },
+                    Key::V => { self.checkpoint(); self.flip_vertically(); },
+                    Key::O if self.shift => {
+                        if let Some(index) = self.placing {
+                            self.patterns[index].1 = self.patterns[index].1.rotated();
+                        }
+                    },
+                    Key::O => { self.checkpoint(); self.rotate(); },
+                    Key::I if self.ctrl => self.cycle_colouring(),
+                    Key::I => { self.checkpoint(); self.board.state.invert(); self.age.fill(0); self.invalidate_history(); },
+                    Key::Z if self.ctrl => self.undo(),
+                    Key::Y if self.ctrl => self.redo(),
+                    Key::LCtrl | Key::RCtrl => self.ctrl = true,
+                    Key::LShift | Key::RShift => self.shift = true,
                     _ => {}
             }
         }
+        if let Some(Button::Keyboard(Key::LCtrl | Key::RCtrl)) = e.release_args() {
+            self.ctrl = false;
+        }
+        if let Some(Button::Keyboard(Key::LShift | Key::RShift)) = e.release_args() {
+            self.shift = false;
+        }
+        if let Some(Button::Keyboard(Key::Equals | Key::Plus | Key::NumPadPlus | Key::Minus | Key::NumPadMinus)) = e.release_args() {
+            if self.speed_held.take().is_some() {
+                self.report_speed();
+            }
+        }
+
+        // Any press, release or drag may have changed the board, or how it is
+        // drawn, including the neighbour counts just toggled on.
+        if e.press_args().is_some() || e.release_args().is_some() || (self.drawing && e.mouse_cursor_args().is_some()) {
+            self.board.refresh();
+            self.board.recount();
+            self.dirty = true;
+        }
+    }
+}
+
+/// [Usage]
+/// Prints what went wrong with the command-line arguments, followed by
+/// how they are meant to be given, then exits with an error.
+fn usage(error: &str) -> ! {
+    eprintln!("{}", error);
+    eprintln!("Usage: game-of-life [threads] [rule] [options]");
+    std::process::exit(2);
+}
+
+/// [Flag Value]
+/// Parses the value following a command-line flag, exiting with the
+/// usage message should it be missing or invalid.
+fn flag_value<T: std::str::FromStr>(args: &mut impl Iterator<Item = String>, flag: &str) -> T {
+    match args.next() {
+        Some(value) => value.parse().unwrap_or_else(|_| usage(&format!("The value \"{}\" given to {} is invalid.", value, flag))),
+        None => usage(&format!("{} must be followed by a value.", flag)),
     }
 }
 
+/// [Theme Index]
+/// Finds the index of the theme with the given name within `THEMES`,
+/// ignoring case. Gives a usage error for unknown names.
+fn theme_index(name: &str) -> usize {
+    THEMES.iter().position(|theme| theme.name.eq_ignore_ascii_case(name)).unwrap_or_else(|| {
+        let names: Vec<&str> = THEMES.iter().map(|theme| theme.name).collect();
+        usage(&format!("The theme \"{}\" must be one of {}.", name, names.join(", ")))
+    })
+}
+
 /// [Main]
 ///
 /// Note: Most of this main method comes from a Piston tutorial.
@@ -253,52 +2986,456 @@ impl App {
 ///
 /// This method sets up the application state, and initializes the OpenGL backend for
 /// execution by Piston.
-
 fn main() {
     // Change this to OpenGL::V2_1 if not working.
     let opengl = OpenGL::V3_2;
 
     // Check to make sure the command-line arguments are valid:
     use std::env;
-    let args = env::args().nth(1);
-    let threads = args.expect("I wasn't given an argument!").parse::<usize>().ok().expect("I wasn't given an integer!");
-    rayon::ThreadPoolBuilder::new().num_threads(threads).build_global().unwrap();
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    // Settings from a config file are the defaults for every other option.
+    let config_path = args.windows(2).find(|pair| pair[0] == "--config").map(|pair| pair[1].clone());
+    let config = match config_path {
+        Some(ref path) => Config::load(path).unwrap_or_else(|e| {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }),
+        None => Config::default(),
+    };
+    let mut args = args.into_iter().peekable();
+
+    // The thread count is optional, as is a count of zero, for which Rayon
+    // sizes its pool to the number of logical CPUs.
+    let threads = match args.peek() {
+        Some(arg) if arg.starts_with(|c: char| c.is_ascii_digit()) => {
+            let arg = args.next().unwrap();
+            arg.parse::<usize>().unwrap_or_else(|_| usage(&format!("The thread count \"{}\" is not a whole number.", arg)))
+        },
+        _ => config.threads.unwrap_or(0),
+    };
+    let threads = game_of_life::configure_threads(threads);
+
+    // The remaining arguments are optional flags, besides the rule, which
+    // defaults to Conway's.
+    let mut rule = config.rule.map_or_else(Rule::conway, |rule| Rule::parse(&rule).unwrap_or_else(|e| usage(&e)));
+    let mut load = None;
+    let mut resume = None;
+    let mut stdin = false;
+    let mut pattern_paths = Vec::new();
+    let mut image = None;
+    let mut seed = None;
+    let mut bench_generations = None;
+    let mut verify_generations = None;
+    let mut compare_generations = None;
+    let mut stress_generations = None;
+    let mut soups = None;
+    let mut soup_side = 16;
+    let mut gif_path = None;
+    let mut gif_frames = 100;
+    let mut gif_delay = 100;
+    let mut density: f64 = config.density.unwrap_or(0.5);
+    let mut mode = UpdateMode::Auto;
+    let mut log_path = None;
+    let mut pause_on_cycle = false;
+    let mut paused = false;
+    let mut verbose = false;
+    let mut thread_stats = false;
+    let mut max_generation = None;
+    let mut pause_at = None;
+    let mut gens_per_frame = 1;
+    let mut timeline_len = 256;
+    let mut expanding = false;
+    let mut asynchronous = false;
+    let mut colouring = None;
+    let mut larger_than_life = None;
+    let mut tile_spacing = 2;
+    let mut symmetric = false;
+    let mut cell_shape = CellShape::Square;
+    let mut neighbourhood = Neighbourhood::Moore;
+    let mut boundary = config.boundary.map_or(Boundary::Torus, |boundary| boundary.parse().unwrap_or_else(|e: String| usage(&e)));
+    let mut theme = config.theme.map_or(0, |name| theme_index(&name));
+    let (mut width, mut height, mut scale) = (WIDTH, HEIGHT, None);
+    let (mut scale_x, mut scale_y) = (None, None);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--config" => { flag_value::<String>(&mut args, "--config"); },
+            "--theme" => theme = theme_index(&flag_value::<String>(&mut args, "--theme")),
+            "--load" => load = Some(flag_value::<String>(&mut args, "--load")),
+            "--resume" => resume = Some(flag_value::<String>(&mut args, "--resume")),
+            "--stdin" => stdin = true,
+            "--pattern" => pattern_paths.push(flag_value::<String>(&mut args, "--pattern")),
+            "--image" => image = Some(flag_value::<String>(&mut args, "--image")),
+            "--bench" => bench_generations = Some(flag_value::<u64>(&mut args, "--bench")),
+            "--verify" => verify_generations = Some(flag_value::<u64>(&mut args, "--verify")),
+            "--compare-bench" => compare_generations = Some(flag_value::<u64>(&mut args, "--compare-bench")),
+            "--stress" => stress_generations = Some(flag_value::<u64>(&mut args, "--stress")),
+            "--soups" => soups = Some(flag_value::<u64>(&mut args, "--soups")),
+            "--soup-side" => soup_side = flag_value(&mut args, "--soup-side"),
+            "--gif" => gif_path = Some(flag_value::<String>(&mut args, "--gif")),
+            "--frames" => gif_frames = flag_value(&mut args, "--frames"),
+            "--delay" => gif_delay = flag_value(&mut args, "--delay"),
+            "--density" => density = flag_value(&mut args, "--density"),
+            "--seed" => seed = Some(flag_value::<u64>(&mut args, "--seed")),
+            "--log" => log_path = Some(flag_value::<String>(&mut args, "--log")),
+            "--pause-on-cycle" => pause_on_cycle = true,
+            "--paused" => paused = true,
+            "--verbose" => verbose = true,
+            "--thread-stats" => thread_stats = true,
+            "--max-gen" => max_generation = Some(flag_value::<u64>(&mut args, "--max-gen")),
+            "--pause-at" => pause_at = Some(flag_value::<u64>(&mut args, "--pause-at")),
+            "--gens-per-frame" => {
+                gens_per_frame = flag_value(&mut args, "--gens-per-frame");
+                if gens_per_frame < 1 {
+                    usage("--gens-per-frame must be at least 1, or the simulation would never advance.");
+                }
+            },
+            "--history" => timeline_len = flag_value(&mut args, "--history"),
+            "--boundary" => boundary = flag_value(&mut args, "--boundary"),
+            "--expand" => expanding = true,
+            "--async" => asynchronous = true,
+            "--immigration" => colouring = Some(Colouring::Immigration),
+            "--quadlife" => colouring = Some(Colouring::QuadLife),
+            "--ltl" => larger_than_life = Some(LargerThanLife::parse(&flag_value::<String>(&mut args, "--ltl")).unwrap_or_else(|e| usage(&e))),
+            "--tile-spacing" => tile_spacing = flag_value(&mut args, "--tile-spacing"),
+            "--symmetric" => symmetric = true,
+            "--shape" => cell_shape = flag_value(&mut args, "--shape"),
+            "--von-neumann" => neighbourhood = Neighbourhood::VonNeumann,
+            "--diagonal" => neighbourhood = Neighbourhood::Diagonal,
+            "--dense" => mode = UpdateMode::Dense,
+            "--sparse" => mode = UpdateMode::Sparse,
+            "--width" => width = flag_value(&mut args, "--width"),
+            "--height" => height = flag_value(&mut args, "--height"),
+            "--scale" => scale = Some(flag_value(&mut args, "--scale")),
+            "--scale-x" => scale_x = Some(flag_value(&mut args, "--scale-x")),
+            "--scale-y" => scale_y = Some(flag_value(&mut args, "--scale-y")),
+            flag if flag.starts_with("--") => usage(&format!("Unknown option {}.", flag)),
+            _ => rule = Rule::parse(&arg).unwrap_or_else(|e| usage(&e)),
+        }
+    }
+
+    // A scale given on the command line overrides both of the config
+    // file's, so the config's per-axis scales only apply without one.
+    let scale = match scale {
+        Some(scale) => [scale_x.unwrap_or(scale), scale_y.unwrap_or(scale)],
+        None => {
+            let scale = config.scale.unwrap_or(SCALE);
+            [scale_x.or(config.scale_x).unwrap_or(scale), scale_y.or(config.scale_y).unwrap_or(scale)]
+        },
+    };
+
+    // Resumed sessions bring their own board, and the window is sized to
+    // fit it at the same scale.
+    let resumed = resume.map(|path| {
+        Session::load(&path)
+            .and_then(|session| Ok((session.board(mode)?, session)))
+            .unwrap_or_else(|e| {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            })
+    });
+    if let Some((ref board, ref session)) = resumed {
+        width = board.cols * scale[0];
+        height = board.rows * scale[1];
+        larger_than_life = board.larger_than_life;
+        density = session.density;
+    }
+
+    // The board must divide evenly into cells, which are only rectangular
+    // when the horizontal and vertical scales differ.
+    if scale.contains(&0) || width == 0 || height == 0 || width % scale[0] != 0 || height % scale[1] != 0 {
+        usage(&format!("The width ({}) and height ({}) must both be non-zero multiples of the horizontal ({}) and vertical ({}) scales.",
+                       width, height, scale[0], scale[1]));
+    }
+    let (cols, rows) = (width / scale[0], height / scale[1]);
+    let size = cols * rows;
+
+    // Larger neighbourhoods must not wrap all the way around onto themselves.
+    if let Some(rule) = larger_than_life {
+        if 2 * rule.radius + 1 > cols.min(rows) {
+            usage(&format!("The radius of {} is too large for a board of {} x {} cells.", rule, cols, rows));
+        }
+    }
+
+    // Densities beyond the range of probabilities are clamped into it.
+    let density = if density.is_nan() { 0.5 } else { density.clamp(0.0, 1.0) };
+
+    // Random boards are reproducible from the seed, which is picked at
+    // random unless one was given or resumed. Soup searches print only CSV,
+    // so leave it out.
+    let seed = resumed.as_ref().map(|(_, session)| session.seed).or(seed).unwrap_or_else(rand::random);
+    if soups.is_none() {
+        println!("Random seed: {}", seed);
+    }
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    // Creating and Populating State Array, either with the given pattern
+    // or image stamped onto the centre of the board, or Randomly
+    let mut state = Cells::new(size);
+    let pattern = if let Some(path) = load {
+        let pattern = std::fs::read_to_string(&path)
+            .map_err(|e| format!("Could not read {}: {}", path, e))
+            .and_then(|text| pattern::parse(&path, &text));
+        Some((path, pattern))
+    } else if stdin {
+        Some((String::from("standard input"), read_stdin()))
+    } else {
+        image.map(|path| {
+            let pattern = image_pattern(&path, cols, rows);
+            (path, pattern)
+        })
+    };
+
+    // Loaded boards start out paused, so that they can be looked over first.
+    let paused = paused || pattern.is_some() || resumed.is_some();
+
+    // Patterns to be placed with the mouse must all load up front.
+    let patterns: Vec<(String, Pattern)> = pattern_paths.into_iter()
+        .map(|path| {
+            let pattern = std::fs::read_to_string(&path)
+                .map_err(|e| format!("Could not read {}: {}", path, e))
+                .and_then(|text| pattern::parse(&path, &text))
+                .unwrap_or_else(|e| {
+                    eprintln!("{}", e);
+                    std::process::exit(1);
+                });
+            (path, pattern)
+        })
+        .collect();
+
+    // state array will determine whether a cell is "alive" or "dead"
+    if let Some((path, pattern)) = pattern {
+        let pattern = pattern.unwrap_or_else(|e| {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        });
+
+        let left = (cols as isize - pattern.width as isize) / 2;
+        let top = (rows as isize - pattern.height as isize) / 2;
+        let clipped = stamp(&mut state, None, cols, rows, &pattern, left, top, Blend::Or);
+        if clipped > 0 {
+            println!("Warning: {} cells of {} lie outside the board, and were clipped.", clipped, path);
+        }
+    } else if symmetric {
+        symmetric_randomize(&mut state, cols, rows, &mut rng, density);
+    } else {
+        randomize(&mut state, &mut rng, density);
+    }
+
+    // Threads only count their work when it is to be reported.
+    stats::enable(thread_stats);
+
+    let mut board = Board::new(cols, rows, state, rule, mode);
+    board.neighbourhood = neighbourhood;
+    board.boundary = boundary;
+    board.expanding = expanding;
+    board.asynchronous = asynchronous;
+    board.larger_than_life = larger_than_life;
+
+    // Resumed boards replace the one just built, carrying on from their generation.
+    let generation = resumed.as_ref().map_or(0, |(_, session)| session.generation);
+    if let Some((resumed, _)) = resumed {
+        board = resumed;
+    }
+
+    // Coloured variants start out with the living cells coloured at random.
+    if let Some(colouring) = colouring {
+        board.colours = Some(immigrant_colours(&board.state, &mut rng, colouring));
+        board.colouring = colouring;
+    }
+
+    // Benchmarks run without ever opening a window. Stress tests are
+    // benchmarks of a board tiled with glider guns, which stays busy
+    // rather than settling down like a random one.
+    if let Some(generations) = stress_generations {
+        let gun = Pattern::from_offsets(pattern::GOSPER_GLIDER_GUN);
+        tile_pattern(&mut board.state, board.colours.as_deref_mut(), cols, rows, &gun, tile_spacing);
+        board.refresh();
+        println!("Tiled the board with Gosper glider guns, {} cells apart", tile_spacing);
+        bench(generations.min(max_generation.unwrap_or(u64::MAX)), board);
+        return;
+    }
+    if let Some(generations) = bench_generations {
+        bench(generations.min(max_generation.unwrap_or(u64::MAX)), board);
+        return;
+    }
+
+    // So do verifications, and recordings, which are written straight from the board.
+    if let Some(generations) = verify_generations {
+        verify(generations, board);
+        return;
+    }
+    if let Some(generations) = compare_generations {
+        compare_bench(generations, board);
+        return;
+    }
+    if let Some(soups) = soups {
+        soup_search(soups, seed, density, soup_side, max_generation.unwrap_or(10_000), board);
+        return;
+    }
+    if let Some(path) = gif_path {
+        // Every frame of a GIF is the size of the first, which a growing board would outgrow.
+        if board.expanding {
+            usage("--gif cannot record an expanding board, as every frame must be the same size.");
+        }
+        if let Err(e) = record_gif(&path, gif_frames, gif_delay, scale, board) {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    // Rows are appended to any existing log, with a header for new ones.
+    let log = log_path.map(|path| {
+        std::fs::OpenOptions::new().create(true).append(true).open(&path)
+            .and_then(|file| {
+                let empty = file.metadata()?.len() == 0;
+                let mut log = BufWriter::new(file);
+                if empty {
+                    writeln!(log, "generation,timestamp,population,update_ms")?;
+                }
+                Ok(log)
+            })
+            .unwrap_or_else(|e| {
+                eprintln!("Could not open {}: {}", path, e);
+                std::process::exit(1);
+            })
+    });
 
     // Create a Glutin window.
-    let mut window: Window = WindowSettings::new( format!("Game of Life ({} Threads) {} x {} Scale = {}", threads, WIDTH, HEIGHT, SCALE), [WIDTH as f64, HEIGHT as f64])
+    let title = if scale[0] == scale[1] {
+        format!("Game of Life ({} Threads) {} x {} Scale = {}", threads, width, height, scale[0])
+    } else {
+        format!("Game of Life ({} Threads) {} x {} Scale = {} x {}", threads, width, height, scale[0], scale[1])
+    };
+    // The window reopens wherever it was, and as large as it was, when the
+    // game last exited, with the board rescaled to match.
+    let geometry = Geometry::load(GEOMETRY_FILE);
+    let window_size = geometry.as_ref().map_or([width as f64, height as f64], |geometry| geometry.size);
+    let mut window: Window = WindowSettings::new(title.clone(), window_size)
         .graphics_api(opengl)
         .exit_on_esc(true)
         .build()
         .unwrap();
+    if let Some(position) = geometry.and_then(|geometry| geometry.position) {
+        window.set_position(position);
+    }
 
-    // Creating and Populating State Array Randomly
-    let mut state: [bool; SIZE] = [false; SIZE];
-    let mut i = 0;
+    // Create the texture the board is rendered to. Every pixel starts out
+    // fully transparent, so the first frame rewrites all of them. Should this
+    // fail, rendering falls back to drawing each living cell as a square.
+    let pixels = vec![0u8; size * 4];
+    let texture = match board_texture(&pixels, cols, rows, Filter::Nearest) {
+        Ok(texture) => Some(texture),
+        Err(e) => {
+            println!("Could not create the board texture ({}), falling back to shapes.", e);
+            None
+        }
+    };
 
-    // state array will determine whether a cell is "alive" or "dead"
-    while i < SIZE {
-        state[i] = rand::random();
-        i = i + 1;
-    }
+    // Load the font for on-screen text.
+    let glyphs = GlyphCache::from_bytes(FONT, (), TextureSettings::new()).expect("Could not load the bundled font!");
 
     // Create a new game, and run it.
     let mut app = App {
         gl: GlGraphics::new(opengl),
-        state: state,
+        board,
+        cell_size: scale,
         cursor_pos: [0.0, 0.0],
-        paused: false,
+        paused,
+        pixels,
+        texture,
+        filter: Filter::Nearest,
+        grid: false,
+        age: vec![0; size],
+        age_colours: false,
+        gen_interval: Duration::ZERO,
+        speed_held: None,
+        last_gen: Instant::now(),
+        offset: [0.0, 0.0],
+        scale: scale.map(|scale| scale as f64),
+        panning: false,
+        brush_radius: 0,
+        drawing: false,
+        drawing_alive: true,
+        press_cursor: [0.0, 0.0],
+        dragging: false,
+        rng,
+        density,
+        glyphs,
+        generation,
+        undo: Vec::new(),
+        redo: Vec::new(),
+        clear_pressed: None,
+        ctrl: false,
+        shift: false,
+        slots: Default::default(),
+        log,
+        history: CycleHistory::default(),
+        pause_on_cycle,
+        max_generation,
+        pause_at,
+        gens_per_frame,
+        window_size: [width as f64, height as f64],
+        selection: None,
+        selecting: false,
+        clipboard: None,
+        fade: vec![0; size],
+        fading: true,
+        theme,
+        following: false,
+        cell_shape,
+        timeline: VecDeque::new(),
+        timeline_pos: 0,
+        timeline_len,
+        minimap: false,
+        minimap_texture: None,
+        minimap_since: Instant::now(),
+        graph: false,
+        populations: VecDeque::new(),
+        patterns,
+        placing: None,
+        blend: Blend::Or,
+        clusters: None,
+        rule_input: None,
+        rule_error: None,
+        edges_shown: false,
+        bounds_shown: false,
+        bounds: None,
+        tile_spacing,
+        verbose,
+        timings: Timings::new(),
+        thread_stats_since: Some(Instant::now()).filter(|_| thread_stats),
+        threads,
+        config_path: config_path.unwrap_or_else(|| String::from("session.toml")),
+        frozen: false,
+        generation_rate: 0.0,
+        dirty: true,
+        frames: 0,
+        generations: 0,
+        rates_since: Instant::now(),
     };
-
+    
     // Count for demonstration's frame-limiter.
     // let mut frame = 0;
 
+    app.resize(window_size);
+
     let mut events = Events::new(EventSettings::new());
     while let Some(e) = events.next(&mut window) {
         app.event([0.0, 0.0], &e);
 
+        // Escape cancels typing in a rule, rather than closing the window.
+        window.set_exit_on_esc(app.rule_input.is_none());
+
         if let Some(args) = e.render_args() {
             app.render(&args);
 
+            // The title shows the rates achieved over roughly the last second.
+            if let Some((fps, gps)) = app.rates() {
+                window.set_title(format!("{} | {:.0} FPS, {:.0} Generations/s", title, fps, gps));
+            }
+
             //frame += 1;
             //if frame == 50 {
             //    break;
@@ -308,5 +3445,44 @@ fn main() {
         if let Some(args) = e.update_args() {
             app.update(&args);
         }
+
+        if app.finished() {
+            println!("Reached generation {} with a population of {}", app.generation, app.board.population);
+            break;
+        }
+    }
+
+    let size = window.size();
+    let geometry = Geometry { position: window.get_position().map(|position| [position.x, position.y]), size: [size.width, size.height] };
+    if let Err(e) = geometry.save(GEOMETRY_FILE) {
+        eprintln!("{}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn undo_then_step_does_not_report_a_cycle() {
+        // A blinker, stepped once and then undone, as `restore` would.
+        let mut state = Cells::new(25);
+        for i in [11, 12, 13] {
+            state.set(i, true);
+        }
+        let mut board = Board::new(5, 5, state.clone(), Rule::conway(), UpdateMode::Dense);
+        let mut history = CycleHistory::default();
+        board.step();
+        assert_eq!(history.observe(&board.state), None);
+        board.replace(5, 5, state);
+        history.clear();
+
+        // Stepping again reaches the same generation, which is no still life.
+        board.step();
+        assert_eq!(history.observe(&board.state), None);
+        board.step();
+        assert_eq!(history.observe(&board.state), None);
+        board.step();
+        assert_eq!(history.observe(&board.state), Some(2));
     }
 }
@@ -0,0 +1,253 @@
+/*****************************************************************/
+//! [Patterns]
+/*****************************************************************/
+//!
+//! Parsing of Game of Life patterns from the file formats they are
+//! commonly distributed in online. Patterns know nothing about the
+//! board they are eventually stamped onto.
+/*****************************************************************/
+
+use board::MAX_SIDE;
+
+/// [Pattern]
+/// A finite arrangement of living cells.
+///
+/// Fields:
+/// [width] Number of columns spanned by the pattern;
+/// [height] Number of rows spanned by the pattern;
+/// [cells] Column and row of every living cell, relative to the upper left corner.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Pattern {
+    pub width: usize,
+    pub height: usize,
+    pub cells: Vec<(usize, usize)>
+}
+
+/// [Glider]
+/// The smallest spaceship, travelling diagonally towards the lower right.
+pub const GLIDER: &[(i32, i32)] = &[
+    (1, 0),
+    (2, 1),
+    (0, 2), (1, 2), (2, 2),
+];
+
+/// [Blinker]
+/// The smallest oscillator, alternating between a row and a column.
+pub const BLINKER: &[(i32, i32)] = &[
+    (0, 0), (1, 0), (2, 0),
+];
+
+/// [Gosper Glider Gun]
+/// The first known gun, firing a glider towards the lower right every
+/// 30 generations.
+pub const GOSPER_GLIDER_GUN: &[(i32, i32)] = &[
+    (24, 0),
+    (22, 1), (24, 1),
+    (12, 2), (13, 2), (20, 2), (21, 2), (34, 2), (35, 2),
+    (11, 3), (15, 3), (20, 3), (21, 3), (34, 3), (35, 3),
+    (0, 4), (1, 4), (10, 4), (16, 4), (20, 4), (21, 4),
+    (0, 5), (1, 5), (10, 5), (14, 5), (16, 5), (17, 5), (22, 5), (24, 5),
+    (10, 6), (16, 6), (24, 6),
+    (11, 7), (15, 7),
+    (12, 8), (13, 8),
+];
+
+impl Pattern {
+
+    /// [From Offsets]
+    /// Builds a pattern from a table of column and row offsets, which
+    /// may be negative. The pattern's upper left corner is placed at the
+    /// smallest offsets, so only the shape of the table matters.
+    pub fn from_offsets(offsets: &[(i32, i32)]) -> Pattern {
+        let left = offsets.iter().map(|&(x, _)| x).min().unwrap_or(0);
+        let top = offsets.iter().map(|&(_, y)| y).min().unwrap_or(0);
+        let cells: Vec<(usize, usize)> = offsets.iter()
+            .map(|&(x, y)| ((x - left) as usize, (y - top) as usize))
+            .collect();
+
+        let width = cells.iter().map(|&(x, _)| x + 1).max().unwrap_or(0);
+        let height = cells.iter().map(|&(_, y)| y + 1).max().unwrap_or(0);
+
+        Pattern { width, height, cells }
+    }
+
+    /// [Rotated]
+    /// The pattern turned a quarter turn clockwise, so that its leftmost
+    /// column becomes its top row.
+    pub fn rotated(&self) -> Pattern {
+        let cells = self.cells.iter()
+            .map(|&(x, y)| (self.height - 1 - y, x))
+            .collect();
+
+        Pattern { width: self.height, height: self.width, cells }
+    }
+}
+
+/// [Parse RLE]
+/// Parses a pattern in the run-length encoded Life format, made up of
+/// an `x = .., y = ..` header followed by runs of `b` (dead) and `o`
+/// (alive) cells, with rows separated by `$` and the pattern ended by
+/// `!`. Lines starting with `#` are comments, and are skipped. Patterns
+/// declared wider or taller than the largest board, `MAX_SIDE` cells, are
+/// rejected, as they could never be placed whole.
+pub fn parse_rle(text: &str) -> Result<Pattern, String> {
+    let mut lines = text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'));
+
+    // The header declares the pattern's dimensions, and optionally its rule.
+    let header = lines.next().ok_or("The RLE file contains no header.")?;
+    let (mut width, mut height) = (None, None);
+    for field in header.split(',') {
+        let mut parts = field.splitn(2, '=').map(str::trim);
+        let (key, value) = (parts.next().unwrap_or(""), parts.next());
+        let value = value.map(|value| value.parse::<usize>());
+        match (key, value) {
+            ("x", Some(Ok(value))) => width = Some(value),
+            ("y", Some(Ok(value))) => height = Some(value),
+            ("x", _) | ("y", _) => return Err(format!("The RLE header \"{}\" has an invalid dimension.", header)),
+            _ => {}
+        }
+    }
+    let (width, height) = match (width, height) {
+        (Some(width), Some(height)) => (width, height),
+        _ => return Err(format!("The RLE header \"{}\" must declare both x and y.", header)),
+    };
+    if width > MAX_SIDE || height > MAX_SIDE {
+        return Err(format!("The RLE header \"{}\" declares a pattern larger than the largest board of {} x {} cells.",
+                           header, MAX_SIDE, MAX_SIDE));
+    }
+
+    // Everything after the header is a stream of optionally counted tokens.
+    // No run may be longer than the pattern is declared to be along its
+    // axis, which keeps absurd counts from exhausting memory.
+    let mut cells = Vec::new();
+    let (mut x, mut y) = (0, 0);
+    let mut count: Option<usize> = None;
+    'tokens: for line in lines {
+        for c in line.chars() {
+            let limit = if c == '$' { height } else { width };
+            if count.is_some_and(|count| count > limit) && !c.is_ascii_digit() {
+                return Err(format!("The RLE file contains a run of {} cells, beyond the declared size of {} x {}.",
+                                   count.unwrap_or(0), width, height));
+            }
+            match c {
+                '0'..='9' => {
+                    let digit = c.to_digit(10).unwrap() as usize;
+                    count = Some(count.unwrap_or(0).checked_mul(10).and_then(|count| count.checked_add(digit))
+                        .ok_or("The RLE file contains a run too long to count.")?);
+                    continue;
+                },
+                'b' | '.' => x += count.unwrap_or(1),
+                '$' => { x = 0; y += count.unwrap_or(1); },
+                '!' => break 'tokens,
+                c if c.is_ascii_alphabetic() => {
+                    for _ in 0..count.unwrap_or(1) {
+                        cells.push((x, y));
+                        x += 1;
+                    }
+                },
+                c if c.is_whitespace() => {},
+                c => return Err(format!("The RLE file contains an unexpected character '{}'.", c)),
+            }
+            count = None;
+        }
+    }
+
+    // Cells beyond the declared dimensions would silently be lost otherwise.
+    let width = cells.iter().map(|&(x, _)| x + 1).fold(width, usize::max);
+    let height = cells.iter().map(|&(_, y)| y + 1).fold(height, usize::max);
+
+    Ok(Pattern { width, height, cells })
+}
+
+/// [Parse Cells]
+/// Parses a pattern in the plaintext `.cells` format, in which each line
+/// is a row of `.` (dead) and `O` (alive) cells. Living cells may also be
+/// written as `*` or `#`, as in other ASCII boards. Lines starting with `!`
+/// are comments, and are skipped. Rows shorter than the widest one are
+/// padded with dead cells.
+pub fn parse_cells(text: &str) -> Result<Pattern, String> {
+    let mut cells = Vec::new();
+    let (mut width, mut height) = (0, 0);
+    for line in text.lines().map(|line| line.trim_end()).filter(|line| !line.starts_with('!')) {
+        for (x, c) in line.chars().enumerate() {
+            match c {
+                '.' => {},
+                'O' | 'o' | '*' | '#' => cells.push((x, height)),
+                c => return Err(format!("The plaintext file contains an unexpected character '{}'.", c)),
+            }
+        }
+        width = width.max(line.chars().count());
+        height += 1;
+    }
+
+    Ok(Pattern { width, height, cells })
+}
+
+/// [Parse]
+/// Parses a pattern in whichever format it is written in, going by the
+/// extension of the file it was read from, or failing that, whether its
+/// first line is a plaintext comment.
+pub fn parse(path: &str, text: &str) -> Result<Pattern, String> {
+    let plaintext = if path.ends_with(".cells") {
+        true
+    } else if path.ends_with(".rle") {
+        false
+    } else {
+        text.trim_start().starts_with('!')
+    };
+
+    if plaintext { parse_cells(text) } else { parse_rle(text) }
+}
+
+/// [Encode RLE]
+/// Encodes a pattern in the run-length encoded Life format, as read by
+/// `parse_rle`. Dead cells at the end of each row are left out, runs
+/// of empty rows are merged into a single counted `$`, and lines are
+/// wrapped so as not to exceed 70 characters.
+pub fn encode_rle(pattern: &Pattern) -> String {
+    const MAX_LINE: usize = 70;
+
+    let mut grid = vec![false; pattern.width * pattern.height];
+    for &(x, y) in &pattern.cells {
+        grid[x + y * pattern.width] = true;
+    }
+
+    // Collect every run as a count and its token, merging pending row breaks.
+    let mut runs: Vec<(usize, char)> = Vec::new();
+    let mut pending_rows = 0;
+    for row in grid.chunks(pattern.width.max(1)).take(pattern.height) {
+        let end = row.iter().rposition(|&alive| alive).map_or(0, |x| x + 1);
+        if end > 0 && pending_rows > 0 {
+            runs.push((pending_rows, '$'));
+            pending_rows = 0;
+        }
+        for &alive in &row[..end] {
+            let token = if alive { 'o' } else { 'b' };
+            match runs.last_mut() {
+                Some(&mut (ref mut count, last)) if last == token => *count += 1,
+                _ => runs.push((1, token)),
+            }
+        }
+        pending_rows += 1;
+    }
+    runs.push((1, '!'));
+
+    // Write the runs out, wrapping lines between runs.
+    let mut text = format!("x = {}, y = {}\n", pattern.width, pattern.height);
+    let mut line = String::new();
+    for (count, token) in runs {
+        let run = if count > 1 { format!("{}{}", count, token) } else { token.to_string() };
+        if line.len() + run.len() > MAX_LINE {
+            text.push_str(&line);
+            text.push('\n');
+            line.clear();
+        }
+        line.push_str(&run);
+    }
+    text.push_str(&line);
+    text.push('\n');
+
+    text
+}
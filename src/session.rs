@@ -0,0 +1,101 @@
+/*****************************************************************/
+//! [Session]
+/*****************************************************************/
+//!
+//! The complete state of a running simulation, saved to and resumed
+//! from a binary `.gol` file. Unlike a config file, this holds the
+//! board itself, along with everything needed to carry on exactly
+//! where it left off. Files begin with a magic number and a format
+//! version, so that files written by older versions can still be
+//! recognised, and anything else rejected outright.
+/*****************************************************************/
+
+use game_of_life::board::{Board, LargerThanLife, Rule, UpdateMode};
+use game_of_life::cells::Cells;
+
+/// Bytes every session file begins with.
+const MAGIC: [u8; 4] = *b"GOL\0";
+
+/// Version of the format written by `save`.
+const VERSION: u32 = 1;
+
+/// [Session]
+/// Everything needed to resume a simulation, as written in its file.
+///
+/// Fields:
+/// [cols] Number of columns on the board;
+/// [rows] Number of rows on the board;
+/// [words] The board's packed cells, as stored by `Cells`;
+/// [generation] Number of generations computed so far;
+/// [rule] Rule in B/S notation, such as `B3/S23`;
+/// [larger_than_life] Larger than Life rule in Golly notation, should one be in use;
+/// [boundary] What lies beyond the edges of the board, as `torus`, `dead` or `reflect`;
+/// [neighbourhood] Cells counted as neighbours, as `moore`, `vonneumann` or `diagonal`;
+/// [asynchronous] Whether cells are updated in place, one at a time;
+/// [expanding] Whether the board grows to make room for cells reaching its edges;
+/// [density] Probability of each cell of a random board starting out alive;
+/// [seed] Seed the random number generator carries on from.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Session {
+    pub cols: usize,
+    pub rows: usize,
+    pub words: Vec<u64>,
+    pub generation: u64,
+    pub rule: String,
+    pub larger_than_life: Option<String>,
+    pub boundary: String,
+    pub neighbourhood: String,
+    pub asynchronous: bool,
+    pub expanding: bool,
+    pub density: f64,
+    pub seed: u64
+}
+
+impl Session {
+
+    /// [Load]
+    /// Reads a session from a `.gol` file, checking its magic number and
+    /// version before decoding the rest.
+    pub fn load(path: &str) -> Result<Session, String> {
+        let bytes = std::fs::read(path).map_err(|e| format!("Could not read {}: {}", path, e))?;
+        if bytes.len() < MAGIC.len() || bytes[..MAGIC.len()] != MAGIC {
+            return Err(format!("{} is not a saved session.", path));
+        }
+
+        let mut body = &bytes[MAGIC.len()..];
+        let version: u32 = bincode::deserialize_from(&mut body).map_err(|e| format!("Could not parse {}: {}", path, e))?;
+        match version {
+            VERSION => bincode::deserialize(body).map_err(|e| format!("Could not parse {}: {}", path, e)),
+            _ => Err(format!("{} was saved in version {} of the format, but only version {} can be read.", path, version, VERSION)),
+        }
+    }
+
+    /// [Save]
+    /// Writes the session to a `.gol` file, replacing anything already
+    /// there.
+    pub fn save(&self, path: &str) -> Result<(), String> {
+        let mut bytes = MAGIC.to_vec();
+        bincode::serialize_into(&mut bytes, &VERSION)
+            .and_then(|()| bincode::serialize_into(&mut bytes, self))
+            .map_err(|e| format!("Could not encode the session: {}", e))?;
+        std::fs::write(path, bytes).map_err(|e| format!("Could not write {}: {}", path, e))
+    }
+
+    /// [Board]
+    /// Rebuilds the board the session was saved from, with the given
+    /// update mode. Gives an error should any part of it be invalid.
+    pub fn board(&self, mode: UpdateMode) -> Result<Board, String> {
+        let state = self.cols.checked_mul(self.rows)
+            .and_then(|size| Cells::from_words(self.words.clone(), size))
+            .filter(|_| self.cols > 0 && self.rows > 0)
+            .ok_or("The saved board's cells do not match its dimensions.")?;
+
+        let mut board = Board::new(self.cols, self.rows, state, Rule::parse(&self.rule)?, mode);
+        board.boundary = self.boundary.parse()?;
+        board.neighbourhood = self.neighbourhood.parse()?;
+        board.asynchronous = self.asynchronous;
+        board.expanding = self.expanding;
+        board.larger_than_life = self.larger_than_life.as_ref().map(|rule| LargerThanLife::parse(rule)).transpose()?;
+        Ok(board)
+    }
+}
@@ -0,0 +1,82 @@
+/*****************************************************************/
+//! [Thread Stats]
+/*****************************************************************/
+//!
+//! Optional diagnostics of how Rayon spreads the work of each dense
+//! generation across its threads. Every thread keeps running totals of
+//! the bands it computed in thread-local storage, so that no thread
+//! ever waits on another to count its work, and the totals are only
+//! gathered together when asked for. Nothing is counted unless enabled,
+//! which leaves a single relaxed load per band on the default path.
+/*****************************************************************/
+
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+/// Whether bands are being counted at all.
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    /// Work done by the current thread since its totals were last collected.
+    static WORK: Cell<ThreadWork> = const { Cell::new(ThreadWork::IDLE) };
+}
+
+/// [Thread Work]
+/// Work done by a single thread since its totals were last collected.
+///
+/// Fields:
+/// [bands] Number of bands of rows computed;
+/// [cells] Number of cells within those bands;
+/// [busy] Time spent computing them.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ThreadWork {
+    pub bands: u64,
+    pub cells: u64,
+    pub busy: Duration
+}
+
+impl ThreadWork {
+
+    /// No work at all.
+    pub const IDLE: ThreadWork = ThreadWork { bands: 0, cells: 0, busy: Duration::ZERO };
+}
+
+/// [Enable]
+/// Starts or stops counting the work done by each thread.
+pub fn enable(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// [Enabled]
+/// Whether the work done by each thread is being counted.
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// [Time Band]
+/// Calls `f` to compute a band of `cells` cells, adding the band and the
+/// time it took to the calling thread's totals while counting is enabled.
+#[inline]
+pub(crate) fn time_band<F: FnOnce()>(cells: usize, f: F) {
+    if !enabled() {
+        return f();
+    }
+
+    let started = Instant::now();
+    f();
+    let busy = started.elapsed();
+    WORK.with(|work| {
+        let totals = work.get();
+        work.set(ThreadWork { bands: totals.bands + 1, cells: totals.cells + cells as u64, busy: totals.busy + busy });
+    });
+}
+
+/// [Collect]
+/// Takes the totals of every thread in the current Rayon pool, in order
+/// of thread index, and starts each of them over. This runs a job on
+/// every thread of the pool at once, so should be called between
+/// generations rather than during one.
+pub fn collect() -> Vec<ThreadWork> {
+    rayon::broadcast(|_| WORK.with(|work| work.replace(ThreadWork::IDLE)))
+}
@@ -0,0 +1,48 @@
+extern crate game_of_life;
+
+/// Runs `generations` generations of a board through `game_of_life::step`.
+fn run(mut cells: Vec<bool>, rows: usize, cols: usize, wrap: bool, generations: usize) -> Vec<bool> {
+    let mut next = vec![false; cells.len()];
+    for _ in 0..generations {
+        game_of_life::step(&cells, &mut next, rows, cols, wrap);
+        std::mem::swap(&mut cells, &mut next);
+    }
+    cells
+}
+
+/// A board with the given cells alive, given as column and row.
+fn board(rows: usize, cols: usize, alive: &[(usize, usize)]) -> Vec<bool> {
+    let mut cells = vec![false; rows * cols];
+    for &(x, y) in alive {
+        cells[x + y * cols] = true;
+    }
+    cells
+}
+
+#[test]
+fn blinker_oscillates() {
+    let row = board(5, 5, &[(1, 2), (2, 2), (3, 2)]);
+    let column = board(5, 5, &[(2, 1), (2, 2), (2, 3)]);
+    assert_eq!(run(row.clone(), 5, 5, false, 1), column);
+    assert_eq!(run(row.clone(), 5, 5, false, 2), row);
+}
+
+#[test]
+fn block_is_still() {
+    let block = board(4, 4, &[(1, 1), (2, 1), (1, 2), (2, 2)]);
+    assert_eq!(run(block.clone(), 4, 4, true, 5), block);
+    assert_eq!(run(block.clone(), 4, 4, false, 5), block);
+}
+
+#[test]
+fn glider_wraps_around_the_torus() {
+    // After 4 generations a glider has moved one cell down and to the right,
+    // so on an 8 x 8 torus it is back where it started after 32.
+    let glider = board(8, 8, &[(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)]);
+    assert_eq!(run(glider.clone(), 8, 8, true, 4), board(8, 8, &[(2, 1), (3, 2), (1, 3), (2, 3), (3, 3)]));
+    assert_eq!(run(glider.clone(), 8, 8, true, 32), glider);
+
+    // Without wrapping, it settles into a block in the corner instead.
+    let settled = run(glider, 8, 8, false, 32);
+    assert_eq!(settled, board(8, 8, &[(6, 6), (7, 6), (6, 7), (7, 7)]));
+}
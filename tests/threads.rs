@@ -0,0 +1,10 @@
+extern crate game_of_life;
+
+// Rayon's global pool belongs to the whole process, so this is the only
+// test in its binary.
+#[test]
+fn configuring_threads_twice_keeps_the_first_pool() {
+    assert_eq!(game_of_life::configure_threads(2), 2);
+    assert_eq!(game_of_life::configure_threads(3), 2);
+    assert_eq!(game_of_life::configure_threads(0), 2);
+}